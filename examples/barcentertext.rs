@@ -1,6 +1,6 @@
 //use colored::{Color, Colorize};
 use colored::Color;
-use termplot_rs::ChartContext;
+use termplot_rs::{ChartContext, HAlign, VAlign};
 
 fn main() {
     let width = 60;
@@ -20,22 +20,22 @@ fn main() {
     let num_bars = data_bars.len();
 
     // Ancho de cada barra en caracteres
-    let bar_char_width = width / num_bars;
-
-    for i in 0..num_bars {
-        let label = labels[i];
-
-        // Calculamos la columna de carácter donde debe empezar para estar centrado
-        let start_col = i * bar_char_width;
-        let center_offset = (bar_char_width as i32 - label.len() as i32) / 2;
-        let target_col = (start_col as i32 + center_offset).max(0) as f64;
-
-        // Convertimos a coordenada normalizada (0.0 - 1.0)
-        // El método .text usa (x_norm * (width - 1)) internamente
-        let x_norm = target_col / (width as f64 - 1.0);
-
-        // y_norm = 0.0 es la base
-        chart.text(label, x_norm, 0.0, Some(Color::White));
+    let bar_char_width = width as f64 / num_bars as f64;
+
+    for (i, label) in labels.iter().enumerate() {
+        // Columna central de la barra, en coordenada normalizada (0.0 - 1.0)
+        let center_col = (i as f64 + 0.5) * bar_char_width;
+        let x_norm = center_col / (width as f64 - 1.0);
+
+        // y_norm = 0.0 es la base; el texto se centra sobre el ancla
+        chart.text_aligned(
+            label,
+            x_norm,
+            0.0,
+            HAlign::Center,
+            VAlign::Bottom,
+            Some(Color::White),
+        );
     }
 
     println!("{}", chart.canvas.render());
@@ -1,4 +1,6 @@
+use crate::text;
 use colored::Color;
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
 use std::fmt::{self, Write};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -9,15 +11,272 @@ pub enum ColorBlend {
     KeepFirst,
 }
 
+/// Regla para decidir qué es "dentro" en polígonos que se cortan a sí mismos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Dentro si una semirrecta cruza un número impar de aristas (deja huecos
+    /// en las zonas solapadas, como el centro de un pentagrama).
+    #[default]
+    EvenOdd,
+    /// Dentro si el número de vueltas es distinto de cero (rellena los solapes).
+    NonZero,
+}
+
+/// Remate de los extremos de un trazo grueso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// El trazo termina justo en el extremo.
+    #[default]
+    Butt,
+    /// Semicírculo de diámetro `width` en cada extremo.
+    Round,
+    /// Prolonga el trazo `width / 2` píxeles por cada extremo.
+    Square,
+}
+
+/// Patrón de discontinuidad: longitudes alternas encendido/apagado en píxeles
+/// (`[4, 2]`), como mucho `DashPattern::MAX`. Vacío = continuo.
+///
+/// Tiene capacidad fija para seguir siendo `Copy` y poder construirse con datos
+/// leídos en tiempo de ejecución.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DashPattern {
+    lengths: [u16; DashPattern::MAX],
+    len: u8,
+}
+
+impl DashPattern {
+    pub const MAX: usize = 8;
+    pub const SOLID: Self = Self {
+        lengths: [0; Self::MAX],
+        len: 0,
+    };
+
+    /// Toma las primeras `MAX` longitudes; las mayores de `u16::MAX` se recortan.
+    pub fn new(lengths: &[usize]) -> Self {
+        let mut pattern = Self::SOLID;
+        for (slot, &len) in pattern.lengths.iter_mut().zip(lengths) {
+            *slot = len.min(u16::MAX as usize) as u16;
+            pattern.len += 1;
+        }
+        pattern
+    }
+
+    pub fn as_slice(&self) -> &[u16] {
+        &self.lengths[..self.len as usize]
+    }
+
+    fn total(&self) -> usize {
+        self.as_slice().iter().map(|&l| l as usize).sum()
+    }
+}
+
+/// Estilo de trazo de `BrailleCanvas`: grosor, discontinuidad y remates.
+///
+/// Se aplica a todo lo que se traza después de fijarlo (líneas, contornos,
+/// curvas y las series de `ChartContext`), pero no a los rellenos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineStyle {
+    /// Grosor en píxeles.
+    pub width: usize,
+    pub dash: DashPattern,
+    pub cap: LineCap,
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        Self::SOLID
+    }
+}
+
+impl LineStyle {
+    pub const SOLID: Self = Self {
+        width: 1,
+        dash: DashPattern::SOLID,
+        cap: LineCap::Butt,
+    };
+
+    pub fn dashed(pattern: &[usize]) -> Self {
+        Self {
+            dash: DashPattern::new(pattern),
+            ..Self::SOLID
+        }
+    }
+
+    pub fn dotted() -> Self {
+        Self::dashed(&[1, 2])
+    }
+
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    fn is_hairline(&self) -> bool {
+        self.width <= 1 && self.dash.total() == 0
+    }
+
+    /// Si el píxel número `offset` del trazo cae en un tramo encendido.
+    fn dash_on(&self, offset: usize) -> bool {
+        let total = self.dash.total();
+        if total == 0 {
+            return true;
+        }
+        let mut pos = offset % total;
+        for (i, &len) in self.dash.as_slice().iter().enumerate() {
+            let len = len as usize;
+            if pos < len {
+                return i % 2 == 0;
+            }
+            pos -= len;
+        }
+        true
+    }
+}
+
+/// Trama con la que se pintan los rellenos, para distinguir áreas sin color.
+///
+/// Se evalúa en coordenadas absolutas de pantalla, así que dos áreas vecinas
+/// con la misma trama encajan sin costuras.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillPattern {
+    #[default]
+    Solid,
+    Checkerboard,
+    /// Rayado diagonal `/`.
+    DiagonalHatch,
+    /// Rayado diagonal `\`.
+    AntiDiagonalHatch,
+    CrossHatch,
+    HorizontalLines,
+    VerticalLines,
+    /// Un punto cada `n` píxeles en cada eje: `Dots(2)` pinta una cuarta parte.
+    Dots(usize),
+}
+
+impl FillPattern {
+    /// Si la trama pinta el píxel `(x, y)` de pantalla.
+    pub fn covers(self, x: usize, y: usize) -> bool {
+        match self {
+            Self::Solid => true,
+            Self::Checkerboard => (x + y).is_multiple_of(2),
+            Self::DiagonalHatch => (x + y).is_multiple_of(4),
+            Self::AntiDiagonalHatch => (x + 4 - y % 4).is_multiple_of(4),
+            Self::CrossHatch => {
+                Self::DiagonalHatch.covers(x, y) || Self::AntiDiagonalHatch.covers(x, y)
+            }
+            Self::HorizontalLines => y.is_multiple_of(4),
+            Self::VerticalLines => x.is_multiple_of(4),
+            Self::Dots(n) => {
+                let n = n.max(1);
+                x.is_multiple_of(n) && y.is_multiple_of(n)
+            }
+        }
+    }
+}
+
+/// Vecindad de `flood_fill`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Solo arriba, abajo, izquierda y derecha: no se escapa por diagonales.
+    #[default]
+    Four,
+    /// También las diagonales.
+    Eight,
+}
+
+/// Contenido de una celda de la capa de texto.
+#[derive(Debug, Clone, PartialEq)]
+enum TextCell {
+    Char(char),
+    /// Grafema de varios `char` (base + marcas combinantes, secuencias ZWJ...).
+    Cluster(Box<str>),
+    /// Segunda celda de un glifo ancho; la ocupa el de su izquierda.
+    Continuation,
+}
+
+impl TextCell {
+    fn new(grapheme: &str) -> Self {
+        let mut chars = grapheme.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::Char(c),
+            _ => Self::Cluster(grapheme.into()),
+        }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Self::Char(c) => text::char_width(*c),
+            Self::Cluster(g) => text::grapheme_width(g),
+            Self::Continuation => 0,
+        }
+    }
+}
+
+/// Recorrido de Bresenham entre dos píxeles, extremos incluidos.
+struct LinePoints {
+    x: isize,
+    y: isize,
+    x1: isize,
+    y1: isize,
+    dx: isize,
+    dy: isize,
+    sx: isize,
+    sy: isize,
+    err: isize,
+    done: bool,
+}
+
+impl Iterator for LinePoints {
+    type Item = (isize, isize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let point = (self.x, self.y);
+        if self.x == self.x1 && self.y == self.y1 {
+            self.done = true;
+            return Some(point);
+        }
+        let e2 = 2 * self.err;
+        if e2 >= self.dy {
+            self.err += self.dy;
+            self.x += self.sx;
+        }
+        if e2 <= self.dx {
+            self.err += self.dx;
+            self.y += self.sy;
+        }
+        Some(point)
+    }
+}
+
 pub struct BrailleCanvas {
     pub width: usize,
     pub height: usize,
     pub blend_mode: ColorBlend,
+    pub line_style: LineStyle,
+    /// Trama de los rellenos (`rect_filled`, `circle_filled`, polígonos, `flood_fill`...).
+    pub fill_pattern: FillPattern,
+    /// Píxeles recorridos por los trazos discontinuos, para que el patrón siga
+    /// entre tramos consecutivos de una polilínea.
+    dash_offset: usize,
+    /// Último píxel de un trazo con estilo; el siguiente tramo no lo cuenta dos veces.
+    stroke_last: Option<(isize, isize)>,
+    /// Dentro de `with_path`: los tramos comparten `dash_offset` y `stroke_last`.
+    /// Fuera, cada primitiva empieza el patrón desde cero.
+    in_path: bool,
     plot_left_inset_px: usize,
     plot_bottom_inset_px: usize,
     buffer: Vec<u8>,
     colors: Vec<Option<Color>>,
-    text_layer: Vec<Option<char>>,
+    text_layer: Vec<Option<TextCell>>,
 }
 
 impl BrailleCanvas {
@@ -27,6 +286,11 @@ impl BrailleCanvas {
             width,
             height,
             blend_mode: ColorBlend::Overwrite,
+            line_style: LineStyle::SOLID,
+            fill_pattern: FillPattern::Solid,
+            dash_offset: 0,
+            stroke_last: None,
+            in_path: false,
             plot_left_inset_px: 0,
             plot_bottom_inset_px: 0,
             buffer: vec![0u8; size],
@@ -51,6 +315,8 @@ impl BrailleCanvas {
         self.text_layer.fill(None);
         self.plot_left_inset_px = 0;
         self.plot_bottom_inset_px = 0;
+        self.dash_offset = 0;
+        self.stroke_last = None;
     }
 
     pub fn set_plot_insets(&mut self, left_px: usize, bottom_px: usize) {
@@ -76,7 +342,7 @@ impl BrailleCanvas {
             if top.buffer[idx] != 0 || top.text_layer[idx].is_some() {
                 self.buffer[idx] = top.buffer[idx];
                 self.colors[idx] = top.colors[idx];
-                self.text_layer[idx] = top.text_layer[idx];
+                self.text_layer[idx] = top.text_layer[idx].clone();
             }
         }
     }
@@ -93,8 +359,8 @@ impl BrailleCanvas {
                 }
             }
 
-            if let Some(ch) = top.text_layer[idx] {
-                self.text_layer[idx] = Some(ch);
+            if let Some(cell) = &top.text_layer[idx] {
+                self.text_layer[idx] = Some(cell.clone());
                 if top.colors[idx].is_some() {
                     self.colors[idx] = top.colors[idx];
                 }
@@ -127,8 +393,8 @@ impl BrailleCanvas {
             self.buffer[idx] &= !background_mask[idx];
             self.buffer[idx] |= top.buffer[idx];
 
-            if let Some(ch) = top.text_layer[idx] {
-                self.text_layer[idx] = Some(ch);
+            if let Some(cell) = &top.text_layer[idx] {
+                self.text_layer[idx] = Some(cell.clone());
             }
 
             let keep_existing_color = top.text_layer[idx].is_none()
@@ -231,6 +497,20 @@ impl BrailleCanvas {
         }
     }
 
+    pub fn is_pixel_set(&self, x: usize, y: usize) -> bool {
+        if y >= self.pixel_height() {
+            return false;
+        }
+        self.is_pixel_set_screen(x, self.pixel_height() - 1 - y)
+    }
+
+    pub fn is_pixel_set_screen(&self, x: usize, y: usize) -> bool {
+        if x >= self.pixel_width() || y >= self.pixel_height() {
+            return false;
+        }
+        self.buffer[self.idx(x / 2, y / 4)] & Self::get_mask(x % 2, y % 4) != 0
+    }
+
     // --- Primitivas con Clipping (Cohen-Sutherland) ---
 
     fn compute_outcode(&self, x: isize, y: isize) -> u8 {
@@ -251,29 +531,25 @@ impl BrailleCanvas {
         code
     }
 
-    fn bresenham(
-        &mut self,
+    /// Recorta el segmento al lienzo (Cohen-Sutherland); `None` si queda fuera.
+    fn clip_line(
+        &self,
         mut x0: isize,
         mut y0: isize,
         mut x1: isize,
         mut y1: isize,
-        color: Option<Color>,
-        cartesian: bool,
-    ) {
+    ) -> Option<(isize, isize, isize, isize)> {
         let w = self.pixel_width() as isize;
         let h = self.pixel_height() as isize;
 
-        // Cohen-Sutherland Clipping
         let mut outcode0 = self.compute_outcode(x0, y0);
         let mut outcode1 = self.compute_outcode(x1, y1);
-        let mut accept = false;
 
         loop {
             if (outcode0 | outcode1) == 0 {
-                accept = true;
-                break;
+                return Some((x0, y0, x1, y1));
             } else if (outcode0 & outcode1) != 0 {
-                break;
+                return None;
             } else {
                 let outcode_out = if outcode0 != 0 { outcode0 } else { outcode1 };
                 let mut x = 0;
@@ -304,45 +580,193 @@ impl BrailleCanvas {
                 }
             }
         }
+    }
 
-        if !accept {
-            return;
-        }
-
+    /// Píxeles del segmento ya recortado, de `(x0, y0)` a `(x1, y1)` incluidos.
+    /// Es un iterador para que el trazo fino siga sin reservar memoria.
+    fn line_points(x0: isize, y0: isize, x1: isize, y1: isize) -> LinePoints {
         let dx = (x1 - x0).abs();
         let dy = -(y1 - y0).abs();
-        let sx = if x0 < x1 { 1 } else { -1 };
-        let sy = if y0 < y1 { 1 } else { -1 };
-        let mut err = dx + dy;
+        LinePoints {
+            x: x0,
+            y: y0,
+            x1,
+            y1,
+            dx,
+            dy,
+            sx: if x0 < x1 { 1 } else { -1 },
+            sy: if y0 < y1 { 1 } else { -1 },
+            err: dx + dy,
+            done: false,
+        }
+    }
+
+    /// Trazo con el `line_style` actual. Fuera de `with_path` es un trazo
+    /// independiente: el patrón de discontinuidad empieza desde cero.
+    fn bresenham(
+        &mut self,
+        x0: isize,
+        y0: isize,
+        x1: isize,
+        y1: isize,
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        if self.line_style.is_hairline() {
+            self.span(x0, y0, x1, y1, color, cartesian);
+        } else {
+            self.with_path(|canvas| canvas.stroke_styled(x0, y0, x1, y1, color, cartesian));
+        }
+    }
 
-        let mut x = x0;
-        let mut y = y0;
+    /// Ejecuta `draw` como una sola polilínea: los tramos que trace continúan el
+    /// patrón de discontinuidad y no repiten el píxel en que se unen. Se puede
+    /// anidar; solo la llamada exterior reinicia el estado.
+    pub(crate) fn with_path<R>(&mut self, draw: impl FnOnce(&mut Self) -> R) -> R {
+        if self.in_path {
+            return draw(self);
+        }
+        self.in_path = true;
+        self.dash_offset = 0;
+        self.stroke_last = None;
+        let result = draw(self);
+        self.in_path = false;
+        self.dash_offset = 0;
+        self.stroke_last = None;
+        result
+    }
 
-        loop {
+    /// Segmento continuo de un píxel, sin estilo.
+    fn span(
+        &mut self,
+        x0: isize,
+        y0: isize,
+        x1: isize,
+        y1: isize,
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        let Some((x0, y0, x1, y1)) = self.clip_line(x0, y0, x1, y1) else {
+            return;
+        };
+        for (x, y) in Self::line_points(x0, y0, x1, y1) {
             if cartesian {
                 self.set_pixel(x as usize, y as usize, color);
             } else {
                 self.set_pixel_screen(x as usize, y as usize, color);
             }
-            if x == x1 && y == y1 {
-                break;
+        }
+    }
+
+    /// Segmento con grosor, discontinuidad y remates. El grosor se aplica en
+    /// perpendicular al eje dominante, así que los extremos quedan rectos salvo
+    /// que el remate diga otra cosa.
+    fn stroke_styled(
+        &mut self,
+        mut x0: isize,
+        mut y0: isize,
+        mut x1: isize,
+        mut y1: isize,
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        let style = self.line_style;
+        let width = style.width.max(1) as isize;
+        let (lo, hi) = (-(width - 1) / 2, width / 2);
+
+        if style.cap == LineCap::Square && width > 1 {
+            let (dx, dy) = ((x1 - x0) as f64, (y1 - y0) as f64);
+            let len = dx.hypot(dy);
+            if len > 0.0 {
+                let ext = width as f64 / 2.0 / len;
+                let (ex, ey) = ((dx * ext).round() as isize, (dy * ext).round() as isize);
+                (x0, y0, x1, y1) = (x0 - ex, y0 - ey, x1 + ex, y1 + ey);
+            }
+        }
+        let x_major = (x1 - x0).abs() >= (y1 - y0).abs();
+        let Some((cx0, cy0, cx1, cy1)) = self.clip_line(x0, y0, x1, y1) else {
+            return;
+        };
+
+        // Bresenham visita un píxel por paso del eje dominante.
+        let last = (cx1 - cx0).abs().max((cy1 - cy0).abs()) as usize;
+        for (i, (x, y)) in Self::line_points(cx0, cy0, cx1, cy1).enumerate() {
+            if i == 0 && self.stroke_last == Some((x, y)) {
+                continue;
+            }
+            let on = style.dash_on(self.dash_offset);
+            self.dash_offset += 1;
+            if !on {
+                continue;
             }
-            let e2 = 2 * err;
-            if e2 >= dy {
-                err += dy;
-                x += sx;
+            for o in lo..=hi {
+                let (px, py) = if x_major { (x, y + o) } else { (x + o, y) };
+                self.plot(px, py, color, cartesian);
             }
-            if e2 <= dx {
-                err += dx;
-                y += sy;
+            if style.cap == LineCap::Round && width > 1 && (i == 0 || i == last) {
+                let r2 = (width as f64 / 2.0).powi(2);
+                for oy in lo..=hi {
+                    for ox in lo..=hi {
+                        let (fx, fy) = (
+                            ox as f64 - (lo + hi) as f64 / 2.0,
+                            oy as f64 - (lo + hi) as f64 / 2.0,
+                        );
+                        if fx * fx + fy * fy <= r2 {
+                            self.plot(x + ox, y + oy, color, cartesian);
+                        }
+                    }
+                }
             }
         }
+        self.stroke_last = Some((cx1, cy1));
+    }
+
+    /// Traza una polilínea con el estilo actual, cerrada si `closed`.
+    fn stroke_path(
+        &mut self,
+        points: &[(isize, isize)],
+        closed: bool,
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        self.with_path(|canvas| {
+            if let [(x, y)] = points {
+                canvas.bresenham(*x, *y, *x, *y, color, cartesian);
+            }
+            for pair in points.windows(2) {
+                canvas.bresenham(pair[0].0, pair[0].1, pair[1].0, pair[1].1, color, cartesian);
+            }
+            if let (true, Some(&first), Some(&last)) =
+                (closed && points.len() > 2, points.first(), points.last())
+            {
+                canvas.bresenham(last.0, last.1, first.0, first.1, color, cartesian);
+            }
+        });
+    }
+
+    /// Ordena desplazamientos desde un centro por ángulo a partir de `from`,
+    /// para recorrer un contorno muestreado por simetría como una polilínea.
+    fn sort_by_angle(offsets: &mut Vec<(isize, isize)>, from: f64) {
+        let angle =
+            |&(dx, dy): &(isize, isize)| ((dy as f64).atan2(dx as f64) - from).rem_euclid(TAU);
+        offsets.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+        offsets.dedup();
     }
 
     pub fn line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, color: Option<Color>) {
         self.bresenham(x0, y0, x1, y1, color, true);
     }
 
+    /// Polilínea abierta por `points`: a diferencia de varias llamadas a `line`,
+    /// el patrón de discontinuidad sigue de un tramo al siguiente.
+    pub fn polyline(&mut self, points: &[(isize, isize)], color: Option<Color>) {
+        self.stroke_path(points, false, color, true);
+    }
+
+    pub fn polyline_screen(&mut self, points: &[(isize, isize)], color: Option<Color>) {
+        self.stroke_path(points, false, color, false);
+    }
+
     pub fn line_screen(
         &mut self,
         x0: isize,
@@ -368,35 +792,57 @@ impl BrailleCanvas {
     pub fn rect_filled(&mut self, x: isize, y: isize, w: usize, h: usize, color: Option<Color>) {
         let max_y = y + h as isize;
         for cy in y..max_y {
-            self.line_screen(x, cy, x + w as isize - 1, cy, color);
+            self.fill_span(x, x + w as isize - 1, cy, color, false);
         }
     }
 
     pub fn circle(&mut self, xc: isize, yc: isize, r: isize, color: Option<Color>) {
-        let mut x = 0;
-        let mut y = r;
-        let mut d = 3 - 2 * r;
+        let octant = Self::circle_octant(r);
+        let reflect = |x: isize, y: isize| {
+            [
+                (x, y),
+                (-x, y),
+                (x, -y),
+                (-x, -y),
+                (y, x),
+                (-y, x),
+                (y, -x),
+                (-y, -x),
+            ]
+        };
 
-        let mut draw_octants = |cx: isize, cy: isize, x: isize, y: isize| {
-            let points = [
-                (cx + x, cy + y),
-                (cx - x, cy + y),
-                (cx + x, cy - y),
-                (cx - x, cy - y),
-                (cx + y, cy + x),
-                (cx - y, cy + x),
-                (cx + y, cy - x),
-                (cx - y, cy - x),
-            ];
-            for (px, py) in points {
+        if !self.line_style.is_hairline() {
+            let mut offsets: Vec<(isize, isize)> =
+                octant.flat_map(|(x, y)| reflect(x, y)).collect();
+            Self::sort_by_angle(&mut offsets, 0.0);
+            let path: Vec<(isize, isize)> =
+                offsets.iter().map(|&(dx, dy)| (xc + dx, yc + dy)).collect();
+            self.stroke_path(&path, true, color, true);
+            return;
+        }
+
+        for (x, y) in octant {
+            for (dx, dy) in reflect(x, y) {
+                let (px, py) = (xc + dx, yc + dy);
                 if px >= 0 && py >= 0 {
                     self.set_pixel(px as usize, py as usize, color);
                 }
             }
-        };
+        }
+    }
 
-        draw_octants(xc, yc, x, y);
-        while y >= x {
+    /// Puntos `(x, y)` del primer octante de la circunferencia de Bresenham.
+    fn circle_octant(r: isize) -> impl Iterator<Item = (isize, isize)> {
+        let (mut x, mut y, mut d) = (0, r, 3 - 2 * r);
+        let mut started = false;
+        std::iter::from_fn(move || {
+            if !started {
+                started = true;
+                return Some((x, y));
+            }
+            if y < x {
+                return None;
+            }
             x += 1;
             if d > 0 {
                 y -= 1;
@@ -404,8 +850,8 @@ impl BrailleCanvas {
             } else {
                 d = d + 4 * x + 6;
             }
-            draw_octants(xc, yc, x, y);
-        }
+            Some((x, y))
+        })
     }
 
     pub fn circle_filled(&mut self, xc: isize, yc: isize, r: isize, color: Option<Color>) {
@@ -414,10 +860,10 @@ impl BrailleCanvas {
         let mut d = 3 - 2 * r;
 
         let mut draw_lines = |cx: isize, cy: isize, x: isize, y: isize| {
-            self.line(cx - x, cy + y, cx + x, cy + y, color);
-            self.line(cx - x, cy - y, cx + x, cy - y, color);
-            self.line(cx - y, cy + x, cx + y, cy + x, color);
-            self.line(cx - y, cy - x, cx + y, cy - x, color);
+            self.fill_span(cx - x, cx + x, cy + y, color, true);
+            self.fill_span(cx - x, cx + x, cy - y, color, true);
+            self.fill_span(cx - y, cx + y, cy + x, color, true);
+            self.fill_span(cx - y, cx + y, cy - x, color, true);
         };
 
         draw_lines(xc, yc, x, y);
@@ -433,81 +879,859 @@ impl BrailleCanvas {
         }
     }
 
-    pub fn set_char(&mut self, col: usize, row: usize, c: char, color: Option<Color>) {
-        let inverted_row = self.height.saturating_sub(1).saturating_sub(row);
-        if col < self.width && inverted_row < self.height {
-            let idx = self.idx(col, inverted_row);
-            self.text_layer[idx] = Some(c);
-            if let Some(col_val) = color {
-                self.colors[idx] = Some(col_val);
-            }
-        }
+    // --- Elipses, Arcos y Rectángulos Redondeados ---
+    //
+    // Las variantes sin sufijo usan coordenadas cartesianas (y hacia arriba) y las
+    // `_screen` coordenadas de pantalla (y hacia abajo). Los ángulos van en
+    // radianes, en sentido antihorario desde +x tal y como se ven en pantalla,
+    // en ambos casos.
+
+    pub fn ellipse(&mut self, xc: isize, yc: isize, rx: isize, ry: isize, color: Option<Color>) {
+        self.ellipse_impl(xc, yc, rx, ry, color, true, false);
     }
 
-    // --- Renderizado Optimizado (Zero Allocation por frame posible) ---
+    pub fn ellipse_screen(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        rx: isize,
+        ry: isize,
+        color: Option<Color>,
+    ) {
+        self.ellipse_impl(xc, yc, rx, ry, color, false, false);
+    }
 
-    /// Helper estático para evitar alocar Strings de `colored` en el formato estándar.
-    fn write_ansi_color<W: Write>(w: &mut W, color: Color) -> fmt::Result {
-        match color {
-            Color::Black => w.write_str("\x1b[30m"),
-            Color::Red => w.write_str("\x1b[31m"),
-            Color::Green => w.write_str("\x1b[32m"),
-            Color::Yellow => w.write_str("\x1b[33m"),
-            Color::Blue => w.write_str("\x1b[34m"),
-            Color::Magenta => w.write_str("\x1b[35m"),
-            Color::Cyan => w.write_str("\x1b[36m"),
-            Color::White => w.write_str("\x1b[37m"),
-            Color::BrightBlack => w.write_str("\x1b[90m"),
-            Color::BrightRed => w.write_str("\x1b[91m"),
-            Color::BrightGreen => w.write_str("\x1b[92m"),
-            Color::BrightYellow => w.write_str("\x1b[93m"),
-            Color::BrightBlue => w.write_str("\x1b[94m"),
-            Color::BrightMagenta => w.write_str("\x1b[95m"),
-            Color::BrightCyan => w.write_str("\x1b[96m"),
-            Color::BrightWhite => w.write_str("\x1b[97m"),
-            Color::TrueColor { r, g, b } => write!(w, "\x1b[38;2;{};{};{}m", r, g, b),
-        }
+    pub fn ellipse_filled(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        rx: isize,
+        ry: isize,
+        color: Option<Color>,
+    ) {
+        self.ellipse_impl(xc, yc, rx, ry, color, true, true);
     }
 
-    pub fn render_to<W: Write>(
-        &self,
-        w: &mut W,
-        show_border: bool,
-        title: Option<&str>,
-    ) -> fmt::Result {
-        if let Some(t) = title {
-            writeln!(w, "{:^width$}", t, width = self.width + 2)?;
-        }
+    pub fn ellipse_filled_screen(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        rx: isize,
+        ry: isize,
+        color: Option<Color>,
+    ) {
+        self.ellipse_impl(xc, yc, rx, ry, color, false, true);
+    }
 
-        if show_border {
-            w.write_char('┌')?;
-            for _ in 0..self.width {
-                w.write_char('─')?;
-            }
-            w.write_char('┐')?;
-            w.write_char('\n')?;
-        }
+    /// Arco de circunferencia de `start` a `end`; si `end < start` da la vuelta.
+    pub fn arc(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        r: isize,
+        start: f64,
+        end: f64,
+        color: Option<Color>,
+    ) {
+        self.arc_impl(xc, yc, r, (start, end), color, true);
+    }
 
-        let mut last_color: Option<Color> = None;
+    pub fn arc_screen(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        r: isize,
+        start: f64,
+        end: f64,
+        color: Option<Color>,
+    ) {
+        self.arc_impl(xc, yc, r, (start, end), color, false);
+    }
 
-        for row in 0..self.height {
-            if show_border {
-                w.write_char('│')?;
-            }
+    /// Sector circular relleno (una porción de tarta) de `start` a `end`.
+    pub fn sector(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        r: isize,
+        start: f64,
+        end: f64,
+        color: Option<Color>,
+    ) {
+        self.sector_impl(xc, yc, r, (start, end), color, true);
+    }
 
-            for col in 0..self.width {
-                let idx = self.idx(col, row);
+    pub fn sector_screen(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        r: isize,
+        start: f64,
+        end: f64,
+        color: Option<Color>,
+    ) {
+        self.sector_impl(xc, yc, r, (start, end), color, false);
+    }
 
-                let char_to_print = if let Some(c) = self.text_layer[idx] {
-                    c
-                } else {
-                    let mask = self.buffer[idx];
-                    std::char::from_u32(0x2800 + mask as u32).unwrap_or(' ')
-                };
+    /// Rectángulo con esquinas de radio `r`; `(x, y)` es la esquina inferior izquierda.
+    pub fn rounded_rect(
+        &mut self,
+        x: isize,
+        y: isize,
+        w: usize,
+        h: usize,
+        r: usize,
+        color: Option<Color>,
+    ) {
+        self.rounded_rect_impl(x, y, (w, h), r, color, true, false);
+    }
 
-                let current_color = self.colors[idx];
+    /// Como `rounded_rect`, con `(x, y)` en la esquina superior izquierda.
+    pub fn rounded_rect_screen(
+        &mut self,
+        x: isize,
+        y: isize,
+        w: usize,
+        h: usize,
+        r: usize,
+        color: Option<Color>,
+    ) {
+        self.rounded_rect_impl(x, y, (w, h), r, color, false, false);
+    }
 
-                if current_color != last_color {
+    pub fn rounded_rect_filled(
+        &mut self,
+        x: isize,
+        y: isize,
+        w: usize,
+        h: usize,
+        r: usize,
+        color: Option<Color>,
+    ) {
+        self.rounded_rect_impl(x, y, (w, h), r, color, true, true);
+    }
+
+    pub fn rounded_rect_filled_screen(
+        &mut self,
+        x: isize,
+        y: isize,
+        w: usize,
+        h: usize,
+        r: usize,
+        color: Option<Color>,
+    ) {
+        self.rounded_rect_impl(x, y, (w, h), r, color, false, true);
+    }
+
+    // --- Curvas ---
+    //
+    // Los puntos de control van en píxeles y admiten fracciones. Cada curva se
+    // subdivide (de Casteljau) hasta que sus tramos se desvían menos de un cuarto
+    // de píxel de la cuerda, y los tramos se trazan con `line`/`line_screen`.
+
+    pub fn quad_bezier(
+        &mut self,
+        p0: (f64, f64),
+        p1: (f64, f64),
+        p2: (f64, f64),
+        color: Option<Color>,
+    ) {
+        let [c1, c2] = Self::quad_to_cubic(p0, p1, p2);
+        self.curve_impl(&[p0, c1, c2, p2], color, true);
+    }
+
+    pub fn quad_bezier_screen(
+        &mut self,
+        p0: (f64, f64),
+        p1: (f64, f64),
+        p2: (f64, f64),
+        color: Option<Color>,
+    ) {
+        let [c1, c2] = Self::quad_to_cubic(p0, p1, p2);
+        self.curve_impl(&[p0, c1, c2, p2], color, false);
+    }
+
+    pub fn cubic_bezier(
+        &mut self,
+        p0: (f64, f64),
+        p1: (f64, f64),
+        p2: (f64, f64),
+        p3: (f64, f64),
+        color: Option<Color>,
+    ) {
+        self.curve_impl(&[p0, p1, p2, p3], color, true);
+    }
+
+    pub fn cubic_bezier_screen(
+        &mut self,
+        p0: (f64, f64),
+        p1: (f64, f64),
+        p2: (f64, f64),
+        p3: (f64, f64),
+        color: Option<Color>,
+    ) {
+        self.curve_impl(&[p0, p1, p2, p3], color, false);
+    }
+
+    /// Spline Catmull-Rom uniforme que pasa por todos los `points`.
+    pub fn catmull_rom(&mut self, points: &[(f64, f64)], color: Option<Color>) {
+        let segments = Self::catmull_rom_segments(points);
+        self.curve_impl(&segments, color, true);
+    }
+
+    pub fn catmull_rom_screen(&mut self, points: &[(f64, f64)], color: Option<Color>) {
+        let segments = Self::catmull_rom_segments(points);
+        self.curve_impl(&segments, color, false);
+    }
+
+    fn quad_to_cubic(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64)) -> [(f64, f64); 2] {
+        [
+            (
+                p0.0 + 2.0 / 3.0 * (p1.0 - p0.0),
+                p0.1 + 2.0 / 3.0 * (p1.1 - p0.1),
+            ),
+            (
+                p2.0 + 2.0 / 3.0 * (p1.0 - p2.0),
+                p2.1 + 2.0 / 3.0 * (p1.1 - p2.1),
+            ),
+        ]
+    }
+
+    /// Convierte la spline en tramos Bézier cúbicos encadenados
+    /// (`p0, c1, c2, p1, c1, c2, p2, ...`), repitiendo los extremos.
+    fn catmull_rom_segments(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let n = points.len();
+        if n < 2 {
+            return points.to_vec();
+        }
+        let mut out = vec![points[0]];
+        for i in 0..n - 1 {
+            let p0 = points[i.saturating_sub(1)];
+            let (p1, p2) = (points[i], points[i + 1]);
+            let p3 = points[(i + 2).min(n - 1)];
+            out.push((p1.0 + (p2.0 - p0.0) / 6.0, p1.1 + (p2.1 - p0.1) / 6.0));
+            out.push((p2.0 - (p3.0 - p1.0) / 6.0, p2.1 - (p3.1 - p1.1) / 6.0));
+            out.push(p2);
+        }
+        out
+    }
+
+    /// Traza tramos cúbicos encadenados: `points` tiene `3k + 1` puntos.
+    pub(crate) fn curve_impl(
+        &mut self,
+        points: &[(f64, f64)],
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        if points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) {
+            return;
+        }
+        let mut polyline = points.first().copied().into_iter().collect::<Vec<_>>();
+        for segment in points.windows(4).step_by(3) {
+            Self::flatten_cubic(
+                [segment[0], segment[1], segment[2], segment[3]],
+                0,
+                &mut polyline,
+            );
+        }
+
+        let rounded = |(x, y): (f64, f64)| (x.round() as isize, y.round() as isize);
+        let Some(&first) = polyline.first() else {
+            return;
+        };
+        self.with_path(|canvas| {
+            let mut prev = rounded(first);
+            if polyline.len() == 1 {
+                canvas.bresenham(prev.0, prev.1, prev.0, prev.1, color, cartesian);
+            }
+            for &point in &polyline[1..] {
+                let next = rounded(point);
+                if next != prev {
+                    canvas.bresenham(prev.0, prev.1, next.0, next.1, color, cartesian);
+                    prev = next;
+                }
+            }
+        });
+    }
+
+    /// Añade a `out` el final de cada tramo plano de la curva (el inicio ya está).
+    fn flatten_cubic(p: [(f64, f64); 4], depth: usize, out: &mut Vec<(f64, f64)>) {
+        let (dx, dy) = (p[3].0 - p[0].0, p[3].1 - p[0].1);
+        let chord = (dx * dx + dy * dy).sqrt();
+        let distance = |q: (f64, f64)| {
+            if chord < 1e-9 {
+                ((q.0 - p[0].0).powi(2) + (q.1 - p[0].1).powi(2)).sqrt()
+            } else {
+                ((q.0 - p[0].0) * dy - (q.1 - p[0].1) * dx).abs() / chord
+            }
+        };
+        if depth >= 16 || distance(p[1]).max(distance(p[2])) <= 0.25 {
+            out.push(p[3]);
+            return;
+        }
+
+        let mid = |a: (f64, f64), b: (f64, f64)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        let (p01, p12, p23) = (mid(p[0], p[1]), mid(p[1], p[2]), mid(p[2], p[3]));
+        let (p012, p123) = (mid(p01, p12), mid(p12, p23));
+        let center = mid(p012, p123);
+        Self::flatten_cubic([p[0], p01, p012, center], depth + 1, out);
+        Self::flatten_cubic([center, p123, p23, p[3]], depth + 1, out);
+    }
+
+    // --- Relleno de Polígonos (scanline) ---
+    //
+    // Se rellenan los píxeles cuyo centro cae dentro del polígono, así que un
+    // cuadrado de lado `n` cubre exactamente `n x n` píxeles. El recorte se hace
+    // por filas y tramos, sin recorrer píxeles fuera del lienzo.
+
+    pub fn polygon_filled(
+        &mut self,
+        vertices: &[(isize, isize)],
+        rule: FillRule,
+        color: Option<Color>,
+    ) {
+        self.polygon_filled_impl(vertices, rule, color, true);
+    }
+
+    pub fn polygon_filled_screen(
+        &mut self,
+        vertices: &[(isize, isize)],
+        rule: FillRule,
+        color: Option<Color>,
+    ) {
+        self.polygon_filled_impl(vertices, rule, color, false);
+    }
+
+    pub fn triangle_filled(
+        &mut self,
+        p0: (isize, isize),
+        p1: (isize, isize),
+        p2: (isize, isize),
+        color: Option<Color>,
+    ) {
+        self.polygon_filled_impl(&[p0, p1, p2], FillRule::EvenOdd, color, true);
+    }
+
+    pub fn triangle_filled_screen(
+        &mut self,
+        p0: (isize, isize),
+        p1: (isize, isize),
+        p2: (isize, isize),
+        color: Option<Color>,
+    ) {
+        self.polygon_filled_impl(&[p0, p1, p2], FillRule::EvenOdd, color, false);
+    }
+
+    fn polygon_filled_impl(
+        &mut self,
+        vertices: &[(isize, isize)],
+        rule: FillRule,
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        if vertices.len() < 3 {
+            return;
+        }
+        let w = self.pixel_width() as isize;
+        let h = self.pixel_height() as isize;
+        let min_y = vertices.iter().map(|p| p.1).min().unwrap_or(0).max(0);
+        let max_y = vertices.iter().map(|p| p.1).max().unwrap_or(-1).min(h - 1);
+
+        // Cruces de la fila con las aristas: (x, sentido de la arista).
+        let mut crossings: Vec<(f64, i32)> = Vec::new();
+        for py in min_y..=max_y {
+            let y = py as f64 + 0.5;
+            crossings.clear();
+            for (i, &(x0, y0)) in vertices.iter().enumerate() {
+                let (x1, y1) = vertices[(i + 1) % vertices.len()];
+                let (x0, y0, x1, y1) = (x0 as f64, y0 as f64, x1 as f64, y1 as f64);
+                if (y0 > y) != (y1 > y) {
+                    let x = x0 + (y - y0) * (x1 - x0) / (y1 - y0);
+                    crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for (k, &(x, dir)) in crossings.iter().enumerate() {
+                winding += dir;
+                let inside = match rule {
+                    FillRule::EvenOdd => k % 2 == 0,
+                    FillRule::NonZero => winding != 0,
+                };
+                let Some(&(next_x, _)) = crossings.get(k + 1) else {
+                    break;
+                };
+                if !inside {
+                    continue;
+                }
+                // Píxeles con centro en [x, next_x).
+                let start = ((x - 0.5).ceil() as isize).max(0);
+                let end = ((next_x - 0.5).ceil() as isize).min(w);
+                for px in start..end {
+                    self.fill_pixel(px, py, color, cartesian);
+                }
+            }
+        }
+    }
+
+    // --- Rellenos ---
+
+    /// Rellena la región de píxeles apagados conectada a `(x, y)`, limitada por
+    /// los píxeles encendidos y los bordes del lienzo. Respeta `fill_pattern`.
+    pub fn flood_fill(
+        &mut self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+        color: Option<Color>,
+    ) {
+        if y >= self.pixel_height() {
+            return;
+        }
+        self.flood_fill_screen(x, self.pixel_height() - 1 - y, connectivity, color);
+    }
+
+    pub fn flood_fill_screen(
+        &mut self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+        color: Option<Color>,
+    ) {
+        let (w, h) = (self.pixel_width(), self.pixel_height());
+        if x >= w || y >= h || self.is_pixel_set_screen(x, y) {
+            return;
+        }
+        let neighbors: &[(isize, isize)] = match connectivity {
+            Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Connectivity::Eight => &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ],
+        };
+
+        // La región se calcula antes de pintar: con una trama, los píxeles que
+        // quedan apagados dentro siguen siendo parte de ella.
+        let mut visited = vec![false; w * h];
+        let mut stack = vec![(x, y)];
+        visited[y * w + x] = true;
+        let mut region = Vec::new();
+        while let Some((cx, cy)) = stack.pop() {
+            region.push((cx, cy));
+            for &(dx, dy) in neighbors {
+                let (nx, ny) = (cx as isize + dx, cy as isize + dy);
+                if nx < 0 || ny < 0 || nx >= w as isize || ny >= h as isize {
+                    continue;
+                }
+                let (nx, ny) = (nx as usize, ny as usize);
+                if !visited[ny * w + nx] && !self.is_pixel_set_screen(nx, ny) {
+                    visited[ny * w + nx] = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+        for (px, py) in region {
+            self.fill_pixel(px as isize, py as isize, color, false);
+        }
+    }
+
+    /// Tramo horizontal de un relleno, recortado y con `fill_pattern`.
+    pub(crate) fn fill_span(
+        &mut self,
+        x0: isize,
+        x1: isize,
+        y: isize,
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        let (w, h) = (self.pixel_width() as isize, self.pixel_height() as isize);
+        if y < 0 || y >= h {
+            return;
+        }
+        // Fila y trama resueltas una vez por tramo, no por píxel.
+        let screen_y = (if cartesian { h - 1 - y } else { y }) as usize;
+        let (lo, hi) = (x0.min(x1).max(0), x0.max(x1).min(w - 1));
+        let pattern = self.fill_pattern;
+        for x in lo..=hi {
+            if pattern.covers(x as usize, screen_y) {
+                self.set_pixel_impl(x as usize, screen_y, color);
+            }
+        }
+    }
+
+    pub(crate) fn fill_pixel(&mut self, x: isize, y: isize, color: Option<Color>, cartesian: bool) {
+        let h = self.pixel_height() as isize;
+        if x < 0 || y < 0 || x >= self.pixel_width() as isize || y >= h {
+            return;
+        }
+        let screen_y = if cartesian { h - 1 - y } else { y };
+        if self.fill_pattern.covers(x as usize, screen_y as usize) {
+            self.set_pixel_impl(x as usize, screen_y as usize, color);
+        }
+    }
+
+    fn plot(&mut self, x: isize, y: isize, color: Option<Color>, cartesian: bool) {
+        if x < 0 || y < 0 || y >= self.pixel_height() as isize {
+            return;
+        }
+        if cartesian {
+            self.set_pixel(x as usize, y as usize, color);
+        } else {
+            self.set_pixel_screen(x as usize, y as usize, color);
+        }
+    }
+
+    /// Puntos del primer cuadrante de una elipse (punto medio), de `(0, ry)` a `(rx, 0)`.
+    fn ellipse_quadrant(rx: isize, ry: isize) -> Vec<(isize, isize)> {
+        let (rx2, ry2) = ((rx * rx) as f64, (ry * ry) as f64);
+        let mut points = Vec::new();
+        let (mut x, mut y) = (0isize, ry);
+
+        // Región 1: pendiente menor que 1, avanza en x.
+        let mut d = ry2 - rx2 * ry as f64 + rx2 / 4.0;
+        while ry2 * x as f64 <= rx2 * y as f64 {
+            points.push((x, y));
+            x += 1;
+            if d < 0.0 {
+                d += ry2 * (2 * x + 1) as f64;
+            } else {
+                y -= 1;
+                d += ry2 * (2 * x + 1) as f64 - 2.0 * rx2 * y as f64;
+            }
+        }
+
+        // Región 2: pendiente mayor que 1, avanza en y.
+        let mut d = ry2 * (x as f64 + 0.5).powi(2) + rx2 * ((y - 1) as f64).powi(2) - rx2 * ry2;
+        while y >= 0 {
+            points.push((x, y));
+            y -= 1;
+            if d > 0.0 {
+                d += rx2 * (1 - 2 * y) as f64;
+            } else {
+                x += 1;
+                d += 2.0 * ry2 * x as f64 + rx2 * (1 - 2 * y) as f64;
+            }
+        }
+        points
+    }
+
+    /// `angle` está dentro del barrido antihorario de `start` a `end`.
+    fn angle_in_sweep(angle: f64, (start, end): (f64, f64)) -> bool {
+        let sweep = end - start;
+        if sweep.abs() >= TAU {
+            return true;
+        }
+        (angle - start).rem_euclid(TAU) <= sweep.rem_euclid(TAU) + 1e-9
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn ellipse_impl(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        rx: isize,
+        ry: isize,
+        color: Option<Color>,
+        cartesian: bool,
+        filled: bool,
+    ) {
+        if rx < 0 || ry < 0 {
+            return;
+        }
+        if rx == 0 || ry == 0 {
+            self.bresenham(xc - rx, yc - ry, xc + rx, yc + ry, color, cartesian);
+            return;
+        }
+        let quadrant = Self::ellipse_quadrant(rx, ry);
+        if !filled && !self.line_style.is_hairline() {
+            let mut offsets: Vec<(isize, isize)> = quadrant
+                .iter()
+                .flat_map(|&(x, y)| [(x, y), (-x, y), (x, -y), (-x, -y)])
+                .collect();
+            Self::sort_by_angle(&mut offsets, 0.0);
+            let path: Vec<(isize, isize)> =
+                offsets.iter().map(|&(dx, dy)| (xc + dx, yc + dy)).collect();
+            self.stroke_path(&path, true, color, cartesian);
+            return;
+        }
+        for (x, y) in quadrant {
+            if filled {
+                self.fill_span(xc - x, xc + x, yc + y, color, cartesian);
+                self.fill_span(xc - x, xc + x, yc - y, color, cartesian);
+            } else {
+                self.plot(xc + x, yc + y, color, cartesian);
+                self.plot(xc - x, yc + y, color, cartesian);
+                self.plot(xc + x, yc - y, color, cartesian);
+                self.plot(xc - x, yc - y, color, cartesian);
+            }
+        }
+    }
+
+    fn arc_impl(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        r: isize,
+        angles: (f64, f64),
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        if r < 0 {
+            return;
+        }
+        // En pantalla el eje y baja, así que el desplazamiento vertical se invierte.
+        let flip = if cartesian { 1 } else { -1 };
+        let mut offsets: Vec<(isize, isize)> = Self::ellipse_quadrant(r, r)
+            .into_iter()
+            .flat_map(|(x, y)| [(x, y), (-x, y), (x, -y), (-x, -y)])
+            .filter(|&(dx, dy)| Self::angle_in_sweep((dy as f64).atan2(dx as f64), angles))
+            .collect();
+
+        if self.line_style.is_hairline() {
+            for (dx, dy) in offsets {
+                self.plot(xc + dx, yc + flip * dy, color, cartesian);
+            }
+            return;
+        }
+        Self::sort_by_angle(&mut offsets, angles.0);
+        let path: Vec<(isize, isize)> = offsets
+            .iter()
+            .map(|&(dx, dy)| (xc + dx, yc + flip * dy))
+            .collect();
+        let full = (angles.1 - angles.0).abs() >= TAU;
+        self.stroke_path(&path, full, color, cartesian);
+    }
+
+    fn sector_impl(
+        &mut self,
+        xc: isize,
+        yc: isize,
+        r: isize,
+        angles: (f64, f64),
+        color: Option<Color>,
+        cartesian: bool,
+    ) {
+        if r < 0 {
+            return;
+        }
+        let flip = if cartesian { 1 } else { -1 };
+        // Mismo umbral que el trazado por punto medio, para que el borde coincida con `arc`.
+        let limit = r * r + r;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let inside = dx * dx + dy * dy <= limit;
+                let center = dx == 0 && dy == 0;
+                if center || (inside && Self::angle_in_sweep((dy as f64).atan2(dx as f64), angles))
+                {
+                    self.fill_pixel(xc + dx, yc + flip * dy, color, cartesian);
+                }
+            }
+        }
+        self.arc_impl(xc, yc, r, angles, color, cartesian);
+    }
+
+    /// La figura es simétrica en vertical, así que `(x, y)` es la esquina de
+    /// menor `y` en ambos sistemas y basta con elegir cómo se pinta cada punto.
+    #[allow(clippy::too_many_arguments)]
+    fn rounded_rect_impl(
+        &mut self,
+        x: isize,
+        y: isize,
+        (w, h): (usize, usize),
+        r: usize,
+        color: Option<Color>,
+        cartesian: bool,
+        filled: bool,
+    ) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        let r = r.min((w - 1) / 2).min((h - 1) / 2) as isize;
+        let (x1, y1) = (x + w as isize - 1, y + h as isize - 1);
+        let (left, right, low, high) = (x + r, x1 - r, y + r, y1 - r);
+        let quadrant = Self::ellipse_quadrant(r, r);
+
+        if !filled && !self.line_style.is_hairline() {
+            // Un único contorno cerrado para que el patrón recorra las esquinas.
+            let corners = [
+                ((left, low), (-1, -1), PI),
+                ((right, low), (1, -1), 1.5 * PI),
+                ((right, high), (1, 1), 0.0),
+                ((left, high), (-1, 1), FRAC_PI_2),
+            ];
+            let mut path = Vec::new();
+            for ((cx, cy), (sx, sy), from) in corners {
+                let mut offsets: Vec<(isize, isize)> = quadrant
+                    .iter()
+                    .map(|&(dx, dy)| (sx * dx, sy * dy))
+                    .collect();
+                Self::sort_by_angle(&mut offsets, from - 0.1);
+                path.extend(offsets.iter().map(|&(dx, dy)| (cx + dx, cy + dy)));
+            }
+            path.dedup();
+            self.stroke_path(&path, true, color, cartesian);
+            return;
+        }
+
+        if filled {
+            for cy in low..=high {
+                self.fill_span(x, x1, cy, color, cartesian);
+            }
+        } else {
+            self.span(left, y, right, y, color, cartesian);
+            self.span(left, y1, right, y1, color, cartesian);
+            self.span(x, low, x, high, color, cartesian);
+            self.span(x1, low, x1, high, color, cartesian);
+        }
+
+        for (dx, dy) in quadrant {
+            if filled {
+                self.fill_span(left - dx, right + dx, low - dy, color, cartesian);
+                self.fill_span(left - dx, right + dx, high + dy, color, cartesian);
+            } else {
+                self.plot(left - dx, low - dy, color, cartesian);
+                self.plot(right + dx, low - dy, color, cartesian);
+                self.plot(left - dx, high + dy, color, cartesian);
+                self.plot(right + dx, high + dy, color, cartesian);
+            }
+        }
+    }
+
+    /// Escribe `c` en la celda `(col, row)` (fila 0 abajo). Un carácter ancho
+    /// ocupa también la celda siguiente; uno de ancho cero (marca combinante) se
+    /// añade al grafema que ya haya en la celda.
+    pub fn set_char(&mut self, col: usize, row: usize, c: char, color: Option<Color>) {
+        let mut buf = [0u8; 4];
+        self.set_grapheme(col, row, c.encode_utf8(&mut buf), color);
+    }
+
+    /// Escribe `text` desde la celda `(col, row)` (fila 0 abajo), grafema a
+    /// grafema, y recorta lo que no quepa sin partir glifos anchos. Devuelve las
+    /// celdas ocupadas.
+    pub fn set_str(&mut self, col: usize, row: usize, text: &str, color: Option<Color>) -> usize {
+        let mut c = col;
+        for g in text::graphemes(text) {
+            let w = text::grapheme_width(g);
+            if w == 0 {
+                // Marcas sueltas al principio: no tienen base a la que unirse.
+                continue;
+            }
+            if c + w > self.width {
+                break;
+            }
+            self.set_grapheme(c, row, g, color);
+            c += w;
+        }
+        c - col
+    }
+
+    fn set_grapheme(&mut self, col: usize, row: usize, grapheme: &str, color: Option<Color>) {
+        let inverted_row = self.height.saturating_sub(1).saturating_sub(row);
+        if col >= self.width || inverted_row >= self.height {
+            return;
+        }
+        let mut idx = self.idx(col, inverted_row);
+
+        if text::grapheme_width(grapheme) == 0 {
+            if col > 0 && self.text_layer[idx] == Some(TextCell::Continuation) {
+                idx -= 1;
+            }
+            let Some(base) = &self.text_layer[idx] else {
+                return;
+            };
+            let mut joined = match base {
+                TextCell::Char(b) => b.to_string(),
+                TextCell::Cluster(g) => g.to_string(),
+                TextCell::Continuation => return,
+            };
+            joined.push_str(grapheme);
+            self.text_layer[idx] = Some(TextCell::new(&joined));
+            return;
+        }
+
+        let cell = TextCell::new(grapheme);
+        let wide = cell.width() == 2 && col + 1 < self.width;
+        self.text_layer[idx] = Some(cell);
+        if let Some(col_val) = color {
+            self.colors[idx] = Some(col_val);
+        }
+        if wide {
+            self.text_layer[idx + 1] = Some(TextCell::Continuation);
+            if let Some(col_val) = color {
+                self.colors[idx + 1] = Some(col_val);
+            }
+        }
+    }
+
+    // --- Renderizado Optimizado (Zero Allocation por frame posible) ---
+
+    /// Helper estático para evitar alocar Strings de `colored` en el formato estándar.
+    pub(crate) fn write_ansi_color<W: Write>(w: &mut W, color: Color) -> fmt::Result {
+        match color {
+            Color::Black => w.write_str("\x1b[30m"),
+            Color::Red => w.write_str("\x1b[31m"),
+            Color::Green => w.write_str("\x1b[32m"),
+            Color::Yellow => w.write_str("\x1b[33m"),
+            Color::Blue => w.write_str("\x1b[34m"),
+            Color::Magenta => w.write_str("\x1b[35m"),
+            Color::Cyan => w.write_str("\x1b[36m"),
+            Color::White => w.write_str("\x1b[37m"),
+            Color::BrightBlack => w.write_str("\x1b[90m"),
+            Color::BrightRed => w.write_str("\x1b[91m"),
+            Color::BrightGreen => w.write_str("\x1b[92m"),
+            Color::BrightYellow => w.write_str("\x1b[93m"),
+            Color::BrightBlue => w.write_str("\x1b[94m"),
+            Color::BrightMagenta => w.write_str("\x1b[95m"),
+            Color::BrightCyan => w.write_str("\x1b[96m"),
+            Color::BrightWhite => w.write_str("\x1b[97m"),
+            Color::TrueColor { r, g, b } => write!(w, "\x1b[38;2;{};{};{}m", r, g, b),
+        }
+    }
+
+    pub fn render_to<W: Write>(
+        &self,
+        w: &mut W,
+        show_border: bool,
+        title: Option<&str>,
+    ) -> fmt::Result {
+        if let Some(t) = title {
+            // Centrado por ancho visible, no por `char`, y recortado al marco.
+            let total = self.width + 2;
+            let t = text::truncate(t, total);
+            let pad = total - text::display_width(&t);
+            let left = pad / 2;
+            writeln!(w, "{:left$}{}{:right$}", "", t, "", right = pad - left)?;
+        }
+
+        if show_border {
+            w.write_char('┌')?;
+            for _ in 0..self.width {
+                w.write_char('─')?;
+            }
+            w.write_char('┐')?;
+            w.write_char('\n')?;
+        }
+
+        let mut last_color: Option<Color> = None;
+
+        for row in 0..self.height {
+            if show_border {
+                w.write_char('│')?;
+            }
+
+            let mut col = 0;
+            while col < self.width {
+                let idx = self.idx(col, row);
+                let current_color = self.colors[idx];
+
+                if current_color != last_color {
                     match current_color {
                         Some(c) => Self::write_ansi_color(w, c)?,
                         None => w.write_str("\x1b[0m")?,
@@ -515,7 +1739,32 @@ impl BrailleCanvas {
                     last_color = current_color;
                 }
 
-                w.write_char(char_to_print)?;
+                // Un glifo ancho solo se escribe si conserva su celda de
+                // continuación; si algo la pisó, se deja un espacio para no
+                // desplazar el resto de la fila. Las continuaciones huérfanas
+                // se pintan como celdas Braille normales.
+                match &self.text_layer[idx] {
+                    Some(cell @ (TextCell::Char(_) | TextCell::Cluster(_)))
+                        if cell.width() == 2 =>
+                    {
+                        let tail = col + 1 < self.width
+                            && self.text_layer[idx + 1] == Some(TextCell::Continuation);
+                        if tail {
+                            Self::write_text_cell(w, cell)?;
+                            col += 1;
+                        } else {
+                            w.write_char(' ')?;
+                        }
+                    }
+                    Some(cell @ (TextCell::Char(_) | TextCell::Cluster(_))) => {
+                        Self::write_text_cell(w, cell)?;
+                    }
+                    _ => {
+                        let mask = self.buffer[idx];
+                        w.write_char(std::char::from_u32(0x2800 + mask as u32).unwrap_or(' '))?;
+                    }
+                }
+                col += 1;
             }
 
             if last_color.is_some() {
@@ -540,6 +1789,14 @@ impl BrailleCanvas {
         Ok(())
     }
 
+    fn write_text_cell<W: Write>(w: &mut W, cell: &TextCell) -> fmt::Result {
+        match cell {
+            TextCell::Char(c) => w.write_char(*c),
+            TextCell::Cluster(g) => w.write_str(g),
+            TextCell::Continuation => Ok(()),
+        }
+    }
+
     pub fn render_with_options(&self, show_border: bool, title: Option<&str>) -> String {
         let mut out = String::with_capacity(self.width * self.height * 2 + 100);
         let _ = self.render_to(&mut out, show_border, title);
@@ -565,8 +1822,9 @@ impl BrailleCanvas {
 
 #[cfg(test)]
 mod tests {
-    use super::BrailleCanvas;
+    use super::{BrailleCanvas, Connectivity, FillPattern, FillRule, LineCap, LineStyle};
     use colored::Color;
+    use std::f64::consts::FRAC_PI_2;
 
     #[test]
     fn overlay_replaces_existing_braille_cells() {
@@ -615,4 +1873,214 @@ mod tests {
         assert_eq!(canvas.colors[0], Some(Color::Green));
         assert_eq!(canvas.render_no_color(), "⣿\n");
     }
+
+    #[test]
+    fn ellipse_spans_both_radii_and_fills_its_interior() {
+        let mut outline = BrailleCanvas::new(4, 2);
+        outline.ellipse_screen(3, 3, 3, 3, None);
+        assert_eq!(outline.render_no_color(), "⡔⠉⠑⡄\n⠑⠤⠔⠁\n");
+
+        let mut filled = BrailleCanvas::new(4, 2);
+        filled.ellipse_filled(3, 3, 3, 3, None);
+        assert_eq!(filled.render_no_color(), "⣠⣶⣦⡀\n⠻⣿⡿⠃\n");
+    }
+
+    #[test]
+    fn arcs_use_visual_angles_in_both_coordinate_systems() {
+        // El primer cuadrante queda arriba a la derecha con cualquier origen.
+        let mut cartesian = BrailleCanvas::new(4, 2);
+        cartesian.arc(3, 3, 3, 0.0, FRAC_PI_2, None);
+        let mut screen = BrailleCanvas::new(4, 2);
+        screen.arc_screen(3, 4, 3, 0.0, FRAC_PI_2, None);
+        assert_eq!(cartesian.render_no_color(), screen.render_no_color());
+        assert_eq!(cartesian.render_no_color(), "⠀⠐⠢⡀\n⠀⠀⠀⠁\n");
+
+        let mut rounded = BrailleCanvas::new(4, 2);
+        rounded.rounded_rect_screen(0, 0, 8, 8, 2, None);
+        assert_eq!(rounded.render_no_color(), "⡎⠉⠉⢱\n⢇⣀⣀⡸\n");
+    }
+
+    #[test]
+    fn curves_with_collinear_control_points_match_a_line() {
+        let mut line = BrailleCanvas::new(4, 2);
+        line.line(0, 0, 7, 7, None);
+
+        let mut bezier = BrailleCanvas::new(4, 2);
+        bezier.cubic_bezier((0.0, 0.0), (2.0, 2.0), (5.0, 5.0), (7.0, 7.0), None);
+        assert_eq!(bezier.render_no_color(), line.render_no_color());
+
+        let mut spline = BrailleCanvas::new(4, 2);
+        spline.catmull_rom(&[(0.0, 0.0), (3.0, 3.0), (7.0, 7.0)], None);
+        assert_eq!(spline.render_no_color(), line.render_no_color());
+    }
+
+    #[test]
+    fn polygon_fill_covers_pixel_centers_and_clips() {
+        let mut square = BrailleCanvas::new(3, 1);
+        square.polygon_filled_screen(&[(0, 0), (4, 0), (4, 4), (0, 4)], FillRule::EvenOdd, None);
+        assert_eq!(square.render_no_color(), "⣿⣿⠀\n");
+
+        // Un triángulo que se sale por todos los lados solo pinta lo visible.
+        let mut clipped = BrailleCanvas::new(2, 1);
+        clipped.triangle_filled((-20, -20), (40, -20), (0, 40), None);
+        assert_eq!(clipped.render_no_color(), "⣿⣿\n");
+    }
+
+    #[test]
+    fn nonzero_fills_the_center_that_even_odd_leaves_open() {
+        let dots = |canvas: &BrailleCanvas| -> u32 {
+            canvas
+                .render_no_color()
+                .chars()
+                .filter(|c| *c != '\n')
+                .map(|c| (c as u32 - 0x2800).count_ones())
+                .sum()
+        };
+        // Pentagrama: el pentágono central tiene número de vueltas 2.
+        let star: Vec<(isize, isize)> = (0..5)
+            .map(|i| {
+                let angle =
+                    std::f64::consts::FRAC_PI_2 + i as f64 * 4.0 * std::f64::consts::PI / 5.0;
+                (
+                    (20.0 + 18.0 * angle.cos()).round() as isize,
+                    (20.0 + 18.0 * angle.sin()).round() as isize,
+                )
+            })
+            .collect();
+
+        let mut even_odd = BrailleCanvas::new(20, 10);
+        even_odd.polygon_filled(&star, FillRule::EvenOdd, None);
+        let mut nonzero = BrailleCanvas::new(20, 10);
+        nonzero.polygon_filled(&star, FillRule::NonZero, None);
+
+        assert!(dots(&nonzero) > dots(&even_odd) + 50);
+        assert_eq!(
+            nonzero.render_no_color().chars().nth(5 * 21 + 10),
+            Some('⣿')
+        );
+        assert_eq!(
+            even_odd.render_no_color().chars().nth(5 * 21 + 10),
+            Some('⠀')
+        );
+    }
+
+    #[test]
+    fn dash_pattern_continues_across_polyline_segments() {
+        let mut single = BrailleCanvas::new(4, 1);
+        single.line_style = LineStyle::dashed(&[2, 1]);
+        single.line_screen(0, 0, 7, 0, None);
+        assert_eq!(single.render_no_color(), "⠉⠈⠁⠉\n");
+
+        // Los tramos de una polilínea comparten extremo sin desplazar el patrón.
+        let mut chained = BrailleCanvas::new(4, 1);
+        chained.line_style = LineStyle::dashed(&[2, 1]);
+        let points: Vec<(isize, isize)> = (0..8).map(|x| (x, 0)).collect();
+        chained.polyline_screen(&points, None);
+        assert_eq!(chained.render_no_color(), single.render_no_color());
+
+        // Líneas sueltas, en cambio, empiezan el patrón desde cero.
+        let mut separate = BrailleCanvas::new(4, 1);
+        separate.line_style = LineStyle::dashed(&[2, 1]);
+        separate.line_screen(0, 3, 7, 3, None);
+        separate.line_screen(0, 0, 7, 0, None);
+        assert_eq!(separate.render_no_color(), "⣉⢈⡁⣉\n");
+    }
+
+    #[test]
+    fn a_stroke_starting_where_another_ended_keeps_its_round_cap() {
+        let style = LineStyle::SOLID.with_width(3).with_cap(LineCap::Round);
+        let mut both = BrailleCanvas::new(6, 3);
+        both.line_style = style.with_cap(LineCap::Butt);
+        both.line_screen(2, 6, 7, 6, None);
+        both.line_style = style;
+        both.line_screen(7, 6, 7, 10, None);
+
+        let mut alone = BrailleCanvas::new(6, 3);
+        alone.line_style = style;
+        alone.line_screen(7, 6, 7, 10, None);
+
+        for (b, a) in both
+            .render_no_color()
+            .chars()
+            .zip(alone.render_no_color().chars())
+        {
+            assert_eq!(b as u32 & a as u32, a as u32);
+        }
+    }
+
+    #[test]
+    fn wide_strokes_grow_across_the_line_but_fills_ignore_the_style() {
+        let mut wide = BrailleCanvas::new(2, 1);
+        wide.line_style = LineStyle::SOLID.with_width(3);
+        wide.line_screen(0, 1, 3, 1, None);
+        assert_eq!(wide.render_no_color(), "⠿⠿\n");
+
+        let mut filled = BrailleCanvas::new(2, 1);
+        filled.line_style = LineStyle::dotted();
+        filled.rect_filled(0, 0, 4, 4, None);
+        assert_eq!(filled.render_no_color(), "⣿⣿\n");
+    }
+
+    #[test]
+    fn four_connected_flood_fill_stops_at_diagonal_walls() {
+        let walled = || {
+            let mut canvas = BrailleCanvas::new(2, 1);
+            canvas.line_screen(0, 0, 3, 3, None);
+            canvas
+        };
+
+        let mut four = walled();
+        four.flood_fill_screen(3, 0, Connectivity::Four, None);
+        assert_eq!(four.render_no_color(), "⠙⢿\n");
+
+        let mut eight = walled();
+        eight.flood_fill_screen(3, 0, Connectivity::Eight, None);
+        assert_eq!(eight.render_no_color(), "⣿⣿\n");
+    }
+
+    #[test]
+    fn fill_patterns_are_anchored_to_the_screen() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        canvas.fill_pattern = FillPattern::Checkerboard;
+        canvas.rect_filled(0, 0, 2, 4, None);
+        canvas.rect_filled(2, 0, 2, 4, None);
+        assert_eq!(canvas.render_no_color(), "⢕⢕\n");
+    }
+
+    #[test]
+    fn wide_glyphs_take_two_cells_and_marks_join_their_base() {
+        let mut canvas = BrailleCanvas::new(6, 1);
+        assert_eq!(canvas.set_str(0, 0, "日本語", None), 6);
+        assert_eq!(canvas.render_with_options(false, None), "日本語\n");
+
+        // Lo que no cabe entero se recorta; nunca queda medio glifo.
+        canvas.clear();
+        assert_eq!(canvas.set_str(1, 0, "日本語", None), 4);
+        assert_eq!(canvas.render_with_options(false, None), "⠀日本⠀\n");
+
+        // Pisar la mitad derecha de un glifo ancho deja un hueco, no desplaza la fila.
+        canvas.set_char(2, 0, 'x', None);
+        assert_eq!(canvas.render_with_options(false, None), "⠀ x本⠀\n");
+
+        canvas.clear();
+        canvas.set_char(0, 0, 'e', None);
+        canvas.set_char(0, 0, '\u{301}', None);
+        canvas.set_str(1, 0, "a\u{308}", None);
+        assert_eq!(
+            canvas.render_with_options(false, None),
+            "e\u{301}a\u{308}⠀⠀⠀⠀\n"
+        );
+    }
+
+    #[test]
+    fn titles_are_centered_and_clipped_by_display_width() {
+        let canvas = BrailleCanvas::new(4, 1);
+        let framed = canvas.render_with_options(true, Some("日本"));
+        let lines: Vec<&str> = framed.lines().collect();
+        assert_eq!(lines[0], " 日本 ");
+        assert_eq!(lines[1], "┌────┐");
+
+        let framed = canvas.render_with_options(true, Some("一二三四"));
+        assert_eq!(framed.lines().next(), Some("一二… "));
+    }
 }
@@ -1,13 +1,89 @@
-use crate::canvas::BrailleCanvas;
+use crate::canvas::{BrailleCanvas, FillRule};
 use crate::scale::AxisScale;
+use crate::text;
 use colored::Color;
-use std::f64::consts::PI;
+
+mod bars;
+mod contour;
+mod density;
+mod distribution;
+mod errors;
+mod funnel;
+mod gantt;
+mod labels;
+mod lines;
+mod markers;
+mod pie;
+mod polar;
+mod regression;
+mod treemap;
+mod vector;
+mod waterfall;
+
+pub use bars::{BarChartOptions, BarLabels, BarLayout, BarOrientation, SeriesPatterns};
+pub use density::ViolinOptions;
+pub use distribution::QqReference;
+pub use errors::ErrorBar;
+pub use funnel::FunnelOptions;
+pub use gantt::{GanttOptions, GanttRow};
+pub use lines::LineInterpolation;
+pub use markers::{Marker, MarkerShape};
+pub use pie::{PieChartOptions, PieLabels};
+pub use polar::RadarOptions;
+pub use regression::MovingAverage;
+pub use treemap::{TreemapNode, TreemapOptions};
+pub use vector::QuiverOptions;
+pub use waterfall::WaterfallOptions;
 
 pub struct ChartContext {
     pub canvas: BrailleCanvas,
     background_mask: Vec<u8>,
     x_scale: AxisScale,
     y_scale: AxisScale,
+    view_range: Option<((f64, f64), (f64, f64))>,
+    polar_r_max: Option<f64>,
+    polar_auto_r_max: Option<f64>,
+}
+
+/// Parámetros de proyección datos -> píxeles copiados del contexto, para poder
+/// mapear dentro de los closures de overlay sin tomar prestado `self`.
+#[derive(Debug, Clone, Copy)]
+struct Projection {
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    x_scale: AxisScale,
+    y_scale: AxisScale,
+    width_px: usize,
+    height_px: usize,
+    left_inset_px: usize,
+    bottom_inset_px: usize,
+}
+
+impl Projection {
+    fn map(&self, x: f64, y: f64) -> Option<(isize, isize)> {
+        let (px, py) = self.map_f64(x, y)?;
+        Some((px.round() as isize, py.round() as isize))
+    }
+
+    /// Como `map`, pero sin redondear al píxel: para medir direcciones y
+    /// longitudes sin el error de cuantización de cada extremo.
+    fn map_f64(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        if !x.is_finite() || !y.is_finite() {
+            return None;
+        }
+        ChartContext::map_coords_f64(
+            x,
+            y,
+            self.x_range,
+            self.y_range,
+            self.x_scale,
+            self.y_scale,
+            self.width_px,
+            self.height_px,
+            self.left_inset_px,
+            self.bottom_inset_px,
+        )
+    }
 }
 
 impl ChartContext {
@@ -18,6 +94,9 @@ impl ChartContext {
             canvas,
             x_scale: AxisScale::Linear,
             y_scale: AxisScale::Linear,
+            view_range: None,
+            polar_r_max: None,
+            polar_auto_r_max: None,
         }
     }
 
@@ -37,6 +116,23 @@ impl ChartContext {
         self
     }
 
+    /// Fija el rango de datos para todos los gráficos posteriores en lugar del
+    /// auto-rango de cada llamada, de modo que varias series (y sus barras de
+    /// error, bandas o ajustes) queden alineadas.
+    pub fn set_view_range(&mut self, x_range: (f64, f64), y_range: (f64, f64)) -> &mut Self {
+        self.view_range = Some((x_range, y_range));
+        self
+    }
+
+    pub fn clear_view_range(&mut self) -> &mut Self {
+        self.view_range = None;
+        self
+    }
+
+    pub fn view_range(&self) -> Option<((f64, f64), (f64, f64))> {
+        self.view_range
+    }
+
     pub fn x_scale(&self) -> AxisScale {
         self.x_scale
     }
@@ -82,6 +178,27 @@ impl ChartContext {
         )
     }
 
+    /// Rango fijado con `set_view_range` o, si no hay, el auto-rango de `points`.
+    fn resolve_ranges(&self, points: &[(f64, f64)]) -> ((f64, f64), (f64, f64)) {
+        self.view_range.unwrap_or_else(|| {
+            Self::get_auto_range_scaled(points, 0.05, self.x_scale, self.y_scale)
+        })
+    }
+
+    fn projection(&self, x_range: (f64, f64), y_range: (f64, f64)) -> Projection {
+        let (left_inset_px, bottom_inset_px) = self.canvas.plot_insets();
+        Projection {
+            x_range,
+            y_range,
+            x_scale: self.x_scale,
+            y_scale: self.y_scale,
+            width_px: self.canvas.pixel_width(),
+            height_px: self.canvas.pixel_height(),
+            left_inset_px,
+            bottom_inset_px,
+        }
+    }
+
     fn default_range(scale: AxisScale) -> (f64, f64) {
         match scale {
             AxisScale::Linear => (0.0, 1.0),
@@ -121,6 +238,34 @@ impl ChartContext {
         left_inset_px: usize,
         bottom_inset_px: usize,
     ) -> Option<(isize, isize)> {
+        let (px, py) = Self::map_coords_f64(
+            x,
+            y,
+            x_range,
+            y_range,
+            x_scale,
+            y_scale,
+            width_px,
+            height_px,
+            left_inset_px,
+            bottom_inset_px,
+        )?;
+        Some((px.round() as isize, py.round() as isize))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn map_coords_f64(
+        x: f64,
+        y: f64,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        x_scale: AxisScale,
+        y_scale: AxisScale,
+        width_px: usize,
+        height_px: usize,
+        left_inset_px: usize,
+        bottom_inset_px: usize,
+    ) -> Option<(f64, f64)> {
         let x_t = x_scale.transform(x)?;
         let y_t = y_scale.transform(y)?;
         let (min_x, max_x) = x_scale.transformed_range(x_range)?;
@@ -131,10 +276,10 @@ impl ChartContext {
         let drawable_width = (width_px.saturating_sub(1 + left_inset_px)).max(1) as f64;
         let drawable_height = (height_px.saturating_sub(1 + bottom_inset_px)).max(1) as f64;
 
-        let px = left_inset_px as f64 + ((x_t - min_x) / range_x * drawable_width).round();
-        let py = bottom_inset_px as f64 + ((y_t - min_y) / range_y * drawable_height).round();
+        let px = left_inset_px as f64 + (x_t - min_x) / range_x * drawable_width;
+        let py = bottom_inset_px as f64 + (y_t - min_y) / range_y * drawable_height;
 
-        Some((px as isize, py as isize))
+        Some((px, py))
     }
 
     fn draw_foreground_overlay<F>(&mut self, draw: F)
@@ -143,6 +288,8 @@ impl ChartContext {
     {
         let mut overlay = BrailleCanvas::new(self.canvas.width, self.canvas.height);
         overlay.blend_mode = self.canvas.blend_mode;
+        overlay.line_style = self.canvas.line_style;
+        overlay.fill_pattern = self.canvas.fill_pattern;
         draw(&mut overlay);
         self.canvas
             .overlay_without_background(&overlay, &self.background_mask);
@@ -154,6 +301,8 @@ impl ChartContext {
     {
         let mut overlay = BrailleCanvas::new(self.canvas.width, self.canvas.height);
         overlay.blend_mode = self.canvas.blend_mode;
+        overlay.line_style = self.canvas.line_style;
+        overlay.fill_pattern = self.canvas.fill_pattern;
         draw(&mut overlay);
         self.canvas.merge(&overlay);
         for (mask, cell) in self.background_mask.iter_mut().zip(overlay.cell_masks()) {
@@ -168,6 +317,7 @@ impl ChartContext {
         points: &[(f64, f64)],
         x_range: (f64, f64),
         y_range: (f64, f64),
+        interpolation: LineInterpolation,
         color: Option<Color>,
     ) {
         let w_px = self.canvas.pixel_width();
@@ -176,31 +326,15 @@ impl ChartContext {
         let x_scale = self.x_scale;
         let y_scale = self.y_scale;
 
-        self.draw_foreground_overlay(|overlay| {
-            for window in points.windows(2) {
-                let (x0, y0) = window[0];
-                let (x1, y1) = window[1];
-                if !x0.is_finite() || !y0.is_finite() || !x1.is_finite() || !y1.is_finite() {
-                    continue;
+        let mapped: Vec<Option<(isize, isize)>> = points
+            .iter()
+            .map(|&(x, y)| {
+                if !x.is_finite() || !y.is_finite() {
+                    return None;
                 }
-
-                let Some(p0) = Self::map_coords(
-                    x0,
-                    y0,
-                    x_range,
-                    y_range,
-                    x_scale,
-                    y_scale,
-                    w_px,
-                    h_px,
-                    left_inset_px,
-                    bottom_inset_px,
-                ) else {
-                    continue;
-                };
-                let Some(p1) = Self::map_coords(
-                    x1,
-                    y1,
+                Self::map_coords(
+                    x,
+                    y,
                     x_range,
                     y_range,
                     x_scale,
@@ -209,20 +343,37 @@ impl ChartContext {
                     h_px,
                     left_inset_px,
                     bottom_inset_px,
-                ) else {
-                    continue;
-                };
-                overlay.line(p0.0, p0.1, p1.0, p1.1, color);
-            }
+                )
+            })
+            .collect();
+
+        // Toda la serie es una polilínea: la discontinuidad sigue entre tramos.
+        self.draw_foreground_overlay(|overlay| {
+            overlay.with_path(|overlay| {
+                if interpolation == LineInterpolation::Smooth {
+                    // La curva necesita los vecinos de cada tramo: se traza por tramos sin huecos.
+                    for run in mapped.split(Option::is_none) {
+                        let run: Vec<(isize, isize)> = run.iter().flatten().copied().collect();
+                        Self::draw_smooth_run(overlay, &run, color);
+                    }
+                    return;
+                }
+                for window in mapped.windows(2) {
+                    if let (Some(p0), Some(p1)) = (window[0], window[1]) {
+                        Self::draw_interpolated_segment(overlay, p0, p1, interpolation, color);
+                    }
+                }
+            });
         });
     }
 
-    pub fn scatter(&mut self, points: &[(f64, f64)], color: Option<Color>) {
-        if points.is_empty() {
-            return;
-        }
-        let (x_range, y_range) =
-            Self::get_auto_range_scaled(points, 0.05, self.x_scale, self.y_scale);
+    fn scatter_with_ranges(
+        &mut self,
+        points: &[(f64, f64)],
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        color: Option<Color>,
+    ) {
         let w_px = self.canvas.pixel_width();
         let h_px = self.canvas.pixel_height();
         let (left_inset_px, bottom_inset_px) = self.canvas.plot_insets();
@@ -256,13 +407,20 @@ impl ChartContext {
         });
     }
 
+    pub fn scatter(&mut self, points: &[(f64, f64)], color: Option<Color>) {
+        if points.is_empty() {
+            return;
+        }
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.scatter_with_ranges(points, x_range, y_range, color);
+    }
+
     pub fn line_chart(&mut self, points: &[(f64, f64)], color: Option<Color>) {
         if points.len() < 2 {
             return;
         }
-        let (x_range, y_range) =
-            Self::get_auto_range_scaled(points, 0.05, self.x_scale, self.y_scale);
-        self.line_chart_with_ranges(points, x_range, y_range, color);
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.line_chart_with_ranges(points, x_range, y_range, LineInterpolation::Linear, color);
     }
 
     pub fn bar_chart(&mut self, values: &[(f64, Option<Color>)]) {
@@ -297,10 +455,13 @@ impl ChartContext {
                         break;
                     }
 
-                    for x in x_start..x_end {
-                        self.canvas
-                            .line(x as isize, 0, x as isize, bar_height as isize, color);
-                    }
+                    self.canvas.rect_filled(
+                        x_start as isize,
+                        h_px as isize - 1 - bar_height as isize,
+                        x_end - x_start,
+                        bar_height + 1,
+                        color,
+                    );
                 }
             }
             AxisScale::Log10 => {
@@ -340,10 +501,13 @@ impl ChartContext {
                         break;
                     }
 
-                    for x in x_start..x_end {
-                        self.canvas
-                            .line(x as isize, 0, x as isize, bar_height as isize, color);
-                    }
+                    self.canvas.rect_filled(
+                        x_start as isize,
+                        h_px as isize - 1 - bar_height as isize,
+                        x_end - x_start,
+                        bar_height + 1,
+                        color,
+                    );
                 }
             }
         }
@@ -353,104 +517,78 @@ impl ChartContext {
         if vertices.len() < 2 {
             return;
         }
+        let (x_range, y_range) = self.polygon_ranges(vertices);
+        let projection = self.projection(x_range, y_range);
+
+        self.draw_foreground_overlay(|overlay| {
+            overlay.with_path(|overlay| {
+                for i in 0..vertices.len() {
+                    let (x0, y0) = vertices[i];
+                    let (x1, y1) = vertices[(i + 1) % vertices.len()];
+                    let (Some(p0), Some(p1)) = (projection.map(x0, y0), projection.map(x1, y1))
+                    else {
+                        continue;
+                    };
+                    overlay.line(p0.0, p0.1, p1.0, p1.1, color);
+                }
+            });
+        });
+    }
+
+    /// Polígono relleno con la regla `rule` y su contorno, con los mismos rangos
+    /// que `polygon`. Los vértices no representables se descartan.
+    pub fn polygon_filled(
+        &mut self,
+        vertices: &[(f64, f64)],
+        rule: FillRule,
+        color: Option<Color>,
+    ) {
+        if vertices.len() < 3 {
+            return;
+        }
+        let (x_range, y_range) = self.polygon_ranges(vertices);
+        let projection = self.projection(x_range, y_range);
+        let pixels: Vec<(isize, isize)> = vertices
+            .iter()
+            .filter_map(|&(x, y)| projection.map(x, y))
+            .collect();
 
+        self.draw_foreground_overlay(|overlay| {
+            overlay.polygon_filled(&pixels, rule, color);
+            overlay.with_path(|overlay| {
+                for (i, &(x0, y0)) in pixels.iter().enumerate() {
+                    let (x1, y1) = pixels[(i + 1) % pixels.len()];
+                    overlay.line(x0, y0, x1, y1, color);
+                }
+            });
+        });
+    }
+
+    /// Vértices normalizados en `[0, 1]` se dibujan sobre el lienzo completo
+    /// salvo que haya un rango fijado o escalas logarítmicas.
+    fn polygon_ranges(&self, vertices: &[(f64, f64)]) -> ((f64, f64), (f64, f64)) {
         let normalized_polygon = vertices.iter().all(|&(x, y)| {
             x.is_finite() && y.is_finite() && (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y)
         });
 
-        let (x_range, y_range) = if normalized_polygon
+        if let Some(view) = self.view_range {
+            view
+        } else if normalized_polygon
             && self.x_scale == AxisScale::Linear
             && self.y_scale == AxisScale::Linear
         {
             ((0.0, 1.0), (0.0, 1.0))
         } else {
             Self::get_auto_range_scaled(vertices, 0.05, self.x_scale, self.y_scale)
-        };
-
-        let w_px = self.canvas.pixel_width();
-        let h_px = self.canvas.pixel_height();
-        let (left_inset_px, bottom_inset_px) = self.canvas.plot_insets();
-        let x_scale = self.x_scale;
-        let y_scale = self.y_scale;
-
-        self.draw_foreground_overlay(|overlay| {
-            for i in 0..vertices.len() {
-                let (x0, y0) = vertices[i];
-                let (x1, y1) = vertices[(i + 1) % vertices.len()];
-                if !x0.is_finite() || !y0.is_finite() || !x1.is_finite() || !y1.is_finite() {
-                    continue;
-                }
-
-                let Some(p0) = Self::map_coords(
-                    x0,
-                    y0,
-                    x_range,
-                    y_range,
-                    x_scale,
-                    y_scale,
-                    w_px,
-                    h_px,
-                    left_inset_px,
-                    bottom_inset_px,
-                ) else {
-                    continue;
-                };
-                let Some(p1) = Self::map_coords(
-                    x1,
-                    y1,
-                    x_range,
-                    y_range,
-                    x_scale,
-                    y_scale,
-                    w_px,
-                    h_px,
-                    left_inset_px,
-                    bottom_inset_px,
-                ) else {
-                    continue;
-                };
-                overlay.line(p0.0, p0.1, p1.0, p1.1, color);
-            }
-        });
+        }
     }
 
     pub fn pie_chart(&mut self, slices: &[(f64, Option<Color>)]) {
-        let total: f64 = slices
+        let labeled: Vec<(&str, f64, Option<Color>)> = slices
             .iter()
-            .filter_map(|(v, _)| {
-                if v.is_finite() && *v > 0.0 {
-                    Some(*v)
-                } else {
-                    None
-                }
-            })
-            .sum();
-        if total <= 1e-9 {
-            return;
-        }
-
-        let w_px = self.canvas.pixel_width() as isize;
-        let h_px = self.canvas.pixel_height() as isize;
-        let cx = w_px / 2;
-        let cy = h_px / 2;
-        let radius = ((w_px.min(h_px).saturating_sub(1)) / 2).max(1);
-        let mut current_angle = 0.0;
-
-        for (value, color) in slices {
-            if !value.is_finite() || *value <= 0.0 {
-                continue;
-            }
-            let slice_angle = (value / total) * 2.0 * PI;
-            let end_angle = current_angle + slice_angle;
-
-            let end_x = cx + (radius as f64 * end_angle.cos()) as isize;
-            let end_y = cy + (radius as f64 * end_angle.sin()) as isize;
-
-            self.draw_foreground_overlay(|overlay| {
-                overlay.line(cx, cy, end_x, end_y, *color);
-            });
-            current_angle = end_angle;
-        }
+            .map(|&(value, color)| ("", value, color))
+            .collect();
+        self.pie_chart_with(&labeled, &PieChartOptions::default());
     }
 
     pub fn draw_circle(&mut self, center: (f64, f64), radius_norm: f64, color: Option<Color>) {
@@ -493,8 +631,12 @@ impl ChartContext {
         if points.len() < 2 {
             return;
         }
-        let (_, y_range) = Self::get_auto_range_scaled(&points, 0.05, self.x_scale, self.y_scale);
-        self.line_chart_with_ranges(&points, (min_x, max_x), y_range, color);
+        let (x_range, y_range) = self.view_range.unwrap_or_else(|| {
+            let (_, y_range) =
+                Self::get_auto_range_scaled(&points, 0.05, self.x_scale, self.y_scale);
+            ((min_x, max_x), y_range)
+        });
+        self.line_chart_with_ranges(&points, x_range, y_range, LineInterpolation::Linear, color);
     }
 
     // --- UTILIDADES ---
@@ -504,13 +646,26 @@ impl ChartContext {
         let h = self.canvas.height;
         let cx = (x_norm * (w.saturating_sub(1)) as f64).round() as usize;
         let cy = (y_norm * (h.saturating_sub(1)) as f64).round() as usize;
+        self.canvas.set_str(cx, cy, text, color);
+    }
 
-        for (i, ch) in text.chars().enumerate() {
-            if cx + i >= w {
+    /// Escribe `text` empezando en la celda `(col, row)` (fila 0 abajo), recortando
+    /// lo que quede fuera del lienzo en lugar de desplazarlo.
+    fn text_at_cell(&mut self, text: &str, col: isize, row: isize, color: Option<Color>) {
+        if row < 0 || row as usize >= self.canvas.height {
+            return;
+        }
+        // Los grafemas que caen a la izquierda del lienzo se descartan enteros.
+        let (mut c, mut rest) = (col, text);
+        for g in text::graphemes(text) {
+            if c >= 0 {
                 break;
             }
-            self.canvas.set_char(cx + i, cy, ch, color);
+            c += text::grapheme_width(g) as isize;
+            rest = &rest[g.len()..];
         }
+        self.canvas
+            .set_str(c.max(0) as usize, row as usize, rest, color);
     }
 
     pub fn draw_axes(&mut self, x_range: (f64, f64), y_range: (f64, f64), color: Option<Color>) {
@@ -551,7 +706,9 @@ impl ChartContext {
             };
             let label = self.x_scale.format_tick(val);
             let margin = if self.canvas.width > 1 {
-                (label.len().saturating_sub(1) as f64 / (self.canvas.width - 1) as f64).min(0.45)
+                (text::display_width(&label).saturating_sub(1) as f64
+                    / (self.canvas.width - 1) as f64)
+                    .min(0.45)
             } else {
                 0.0
             };
@@ -581,6 +738,7 @@ impl ChartContext {
 #[cfg(test)]
 mod tests {
     use super::ChartContext;
+    use crate::canvas::LineStyle;
     use crate::scale::AxisScale;
 
     fn visible_render(chart: &ChartContext) -> String {
@@ -680,4 +838,20 @@ mod tests {
             .iter()
             .any(|row| row.chars().last().unwrap_or(blank) != blank));
     }
+
+    #[test]
+    fn bar_chart_fills_ignore_the_line_style() {
+        let values = [(1.0, None), (3.0, None), (2.0, None)];
+        let mut solid = ChartContext::new(6, 3);
+        solid.bar_chart(&values);
+
+        let mut styled = ChartContext::new(6, 3);
+        styled.canvas.line_style = LineStyle::dashed(&[2, 2]).with_width(3);
+        styled.bar_chart(&values);
+        assert_eq!(
+            styled.canvas.render_no_color(),
+            solid.canvas.render_no_color()
+        );
+        assert_eq!(solid.canvas.render_no_color(), "⠀⠀⣿⣿⣀⣀\n⣀⣀⣿⣿⣿⣿\n⣿⣿⣿⣿⣿⣿\n");
+    }
 }
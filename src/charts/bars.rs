@@ -0,0 +1,511 @@
+use super::ChartContext;
+use crate::canvas::FillPattern;
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+
+/// Cómo se reparten varias series dentro de una misma categoría.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarLayout {
+    /// Una barra por serie, una al lado de la otra.
+    Grouped,
+    /// Las series se apilan; positivos hacia arriba y negativos hacia abajo.
+    Stacked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarOrientation {
+    Vertical,
+    Horizontal,
+}
+
+/// Dónde se escribe el valor numérico de cada barra (capa de texto).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarLabels {
+    None,
+    /// Dentro de la barra, junto a su extremo.
+    Inside,
+    /// Fuera de la barra, a continuación de su extremo.
+    Above,
+}
+
+/// Trama de cada serie de `BarChartOptions`, como mucho `SeriesPatterns::MAX`.
+/// Tiene capacidad fija para que las opciones sigan siendo `Copy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SeriesPatterns {
+    patterns: [FillPattern; SeriesPatterns::MAX],
+    len: usize,
+}
+
+impl SeriesPatterns {
+    pub const MAX: usize = 8;
+
+    /// Toma las primeras `MAX` tramas.
+    pub fn new(patterns: &[FillPattern]) -> Self {
+        let mut cycle = Self::default();
+        for (slot, &pattern) in cycle.patterns.iter_mut().zip(patterns) {
+            *slot = pattern;
+            cycle.len += 1;
+        }
+        cycle
+    }
+
+    pub fn as_slice(&self) -> &[FillPattern] {
+        &self.patterns[..self.len]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarChartOptions {
+    pub layout: BarLayout,
+    pub orientation: BarOrientation,
+    /// Separación en píxeles entre categorías.
+    pub gap_px: usize,
+    /// Separación en píxeles entre barras de la misma categoría (solo `Grouped`).
+    pub series_gap_px: usize,
+    pub labels: BarLabels,
+    /// Dibuja la línea base del cero (o el mínimo en escala logarítmica).
+    pub show_baseline: bool,
+    pub baseline_color: Option<Color>,
+    pub label_color: Option<Color>,
+    /// Trama de cada serie (se repite si hay más series); vacío = la del lienzo.
+    /// Distingue las series apiladas sin depender del color.
+    pub patterns: SeriesPatterns,
+}
+
+impl Default for BarChartOptions {
+    fn default() -> Self {
+        Self {
+            layout: BarLayout::Grouped,
+            orientation: BarOrientation::Vertical,
+            gap_px: 2,
+            series_gap_px: 0,
+            labels: BarLabels::None,
+            show_baseline: true,
+            baseline_color: None,
+            label_color: None,
+            patterns: SeriesPatterns::default(),
+        }
+    }
+}
+
+impl BarChartOptions {
+    pub fn stacked() -> Self {
+        Self {
+            layout: BarLayout::Stacked,
+            ..Self::default()
+        }
+    }
+
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = BarOrientation::Horizontal;
+        self
+    }
+
+    pub fn with_gap(mut self, gap_px: usize) -> Self {
+        self.gap_px = gap_px;
+        self
+    }
+
+    pub fn with_labels(mut self, labels: BarLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn with_patterns(mut self, patterns: &[FillPattern]) -> Self {
+        self.patterns = SeriesPatterns::new(patterns);
+        self
+    }
+}
+
+/// Segmento ya resuelto de una barra: `[start, end]` sobre el eje de categorías y
+/// `[from, to]` sobre el eje de valores (ambos en unidades del eje, sin escalar).
+struct BarSegment {
+    start: f64,
+    end: f64,
+    from: f64,
+    to: f64,
+    color: Option<Color>,
+    label: Option<f64>,
+    /// Posición de la serie dentro de su categoría.
+    series: usize,
+}
+
+impl ChartContext {
+    /// Gráfico de barras con varias series por categoría.
+    ///
+    /// Cada elemento de `categories` contiene un `(valor, color)` por serie. El eje de
+    /// valores usa `y_scale` en vertical y `x_scale` en horizontal; en escala lineal
+    /// el rango siempre incluye el cero para que los negativos crezcan hacia abajo.
+    pub fn bar_chart_with<C>(&mut self, categories: &[C], options: &BarChartOptions)
+    where
+        C: AsRef<[(f64, Option<Color>)]>,
+    {
+        if categories.is_empty() {
+            return;
+        }
+
+        let vertical = options.orientation == BarOrientation::Vertical;
+        let value_scale = if vertical { self.y_scale } else { self.x_scale };
+        let Some((min_v, max_v)) = Self::bar_value_range(categories, options.layout, value_scale)
+        else {
+            return;
+        };
+        let baseline = match value_scale {
+            AxisScale::Linear => 0.0,
+            AxisScale::Log10 => min_v,
+        };
+
+        let w_px = self.canvas.pixel_width();
+        let h_px = self.canvas.pixel_height();
+        let (left_inset_px, bottom_inset_px) = self.canvas.plot_insets();
+        let (cat_origin, cat_len, val_origin, val_len) = if vertical {
+            (
+                left_inset_px as f64,
+                w_px.saturating_sub(left_inset_px) as f64,
+                bottom_inset_px as f64,
+                h_px.saturating_sub(bottom_inset_px).max(1) as f64,
+            )
+        } else {
+            (
+                bottom_inset_px as f64,
+                h_px.saturating_sub(bottom_inset_px) as f64,
+                left_inset_px as f64,
+                w_px.saturating_sub(left_inset_px).max(1) as f64,
+            )
+        };
+
+        let Some((t_min, t_max)) = value_scale.transformed_range((min_v, max_v)) else {
+            return;
+        };
+        let t_span = (t_max - t_min).max(1e-9);
+        let value_to_px = |value: f64| -> f64 {
+            let t = value_scale.transform(value).unwrap_or(t_min);
+            val_origin + ((t - t_min) / t_span * val_len).round()
+        };
+
+        let segments = Self::bar_segments(categories, options, cat_origin, cat_len, baseline);
+
+        let baseline_px = (value_to_px(baseline) as isize).min((val_origin + val_len) as isize - 1);
+        let cat_end = (cat_origin + cat_len - 1.0) as isize;
+        let baseline_color = options.baseline_color;
+        let show_baseline = options.show_baseline;
+
+        let patterns = options.patterns;
+        let patterns = patterns.as_slice();
+
+        self.draw_foreground_overlay(|overlay| {
+            let canvas_pattern = overlay.fill_pattern;
+            for seg in &segments {
+                let v0 = value_to_px(seg.from) as isize;
+                let v1 = value_to_px(seg.to) as isize;
+                // Intervalo semiabierto: los segmentos apilados no se solapan.
+                let (lo, hi) = (v0.min(v1), v0.max(v1) - 1);
+                if hi < lo {
+                    continue;
+                }
+                overlay.fill_pattern = if patterns.is_empty() {
+                    canvas_pattern
+                } else {
+                    patterns[seg.series % patterns.len()]
+                };
+                let (start, end) = (seg.start as isize, seg.end as isize);
+                let (across, along) = ((end - start + 1) as usize, (hi - lo + 1) as usize);
+                let top = h_px as isize - 1;
+                if vertical {
+                    overlay.rect_filled(start, top - hi, across, along, seg.color);
+                } else {
+                    // En horizontal la primera categoría va arriba.
+                    let y = start - cat_origin as isize;
+                    overlay.rect_filled(lo, y, along, across, seg.color);
+                }
+            }
+            overlay.fill_pattern = canvas_pattern;
+
+            if show_baseline {
+                if vertical {
+                    overlay.line(
+                        cat_origin as isize,
+                        baseline_px,
+                        cat_end,
+                        baseline_px,
+                        baseline_color,
+                    );
+                } else {
+                    let top = h_px as isize - 1;
+                    let bottom = top - (cat_end - cat_origin as isize);
+                    overlay.line(baseline_px, bottom, baseline_px, top, baseline_color);
+                }
+            }
+        });
+
+        if options.labels == BarLabels::None {
+            return;
+        }
+
+        for seg in &segments {
+            let Some(value) = seg.label else {
+                continue;
+            };
+            let text = value_scale.format_tick(value);
+            let len = text::display_width(&text) as isize;
+            let grows_up = seg.to >= seg.from;
+            // Último píxel encendido de la barra en su sentido de crecimiento.
+            let tip_px = value_to_px(seg.to) as isize - isize::from(grows_up);
+            let inside = options.labels == BarLabels::Inside;
+            let label_color = options.label_color.or(seg.color);
+
+            if vertical {
+                let center_col = ((seg.start + seg.end) / 2.0 / 2.0).round() as isize;
+                let tip_row = tip_px.div_euclid(4);
+                let mut row = match (inside, grows_up) {
+                    (true, _) => tip_row,
+                    (false, true) => tip_row + 1,
+                    (false, false) => tip_row - 1,
+                };
+                // Sin sitio fuera de la barra: la etiqueta pasa a ir dentro.
+                if row < 0 || row as usize >= self.canvas.height {
+                    row = tip_row;
+                }
+                self.text_at_cell(&text, center_col - len / 2, row, label_color);
+            } else {
+                let center_px = (h_px as f64 - 1.0) - ((seg.start + seg.end) / 2.0 - cat_origin);
+                let row = (center_px / 4.0).floor() as isize;
+                let tip_col = tip_px.div_euclid(2);
+                let inside_col = if grows_up { tip_col - len + 1 } else { tip_col };
+                let mut col = match (inside, grows_up) {
+                    (true, _) => inside_col,
+                    (false, true) => tip_col + 1,
+                    (false, false) => tip_col - len,
+                };
+                if col < 0 || (col + len) as usize > self.canvas.width {
+                    col = inside_col;
+                }
+                self.text_at_cell(&text, col, row, label_color);
+            }
+        }
+    }
+
+    fn bar_value_range<C>(
+        categories: &[C],
+        layout: BarLayout,
+        scale: AxisScale,
+    ) -> Option<(f64, f64)>
+    where
+        C: AsRef<[(f64, Option<Color>)]>,
+    {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        for category in categories {
+            let values = category.as_ref().iter().map(|(v, _)| *v);
+            match layout {
+                BarLayout::Grouped => {
+                    for v in values.filter(|v| scale.transform(*v).is_some()) {
+                        min = min.min(v);
+                        max = max.max(v);
+                    }
+                }
+                BarLayout::Stacked => {
+                    let (pos, neg) =
+                        values
+                            .filter(|v| v.is_finite())
+                            .fold(
+                                (0.0, 0.0),
+                                |(p, n), v| if v >= 0.0 { (p + v, n) } else { (p, n + v) },
+                            );
+                    if scale.transform(pos).is_some() {
+                        max = max.max(pos);
+                        min = min.min(pos);
+                    }
+                    if neg < 0.0 && scale == AxisScale::Linear {
+                        min = min.min(neg);
+                    }
+                }
+            }
+        }
+
+        if !min.is_finite() || !max.is_finite() {
+            return None;
+        }
+
+        match scale {
+            AxisScale::Linear => {
+                let (min, max) = (min.min(0.0), max.max(0.0));
+                if (max - min).abs() < 1e-9 {
+                    return None;
+                }
+                Some((min, max))
+            }
+            // Sin cero en escala log: la barra más baja conserva un píxel de altura.
+            AxisScale::Log10 => {
+                let min_t = min.log10();
+                let max_t = max.log10().max(min_t + 1e-9);
+                let pad = (max_t - min_t).max(1.0) * 0.05;
+                Some((10f64.powf(min_t - pad), 10f64.powf(max_t)))
+            }
+        }
+    }
+
+    fn bar_segments<C>(
+        categories: &[C],
+        options: &BarChartOptions,
+        origin: f64,
+        length: f64,
+        baseline: f64,
+    ) -> Vec<BarSegment>
+    where
+        C: AsRef<[(f64, Option<Color>)]>,
+    {
+        let slot = length / categories.len() as f64;
+        let mut segments = Vec::new();
+
+        for (i, category) in categories.iter().enumerate() {
+            let values = category.as_ref();
+            if values.is_empty() {
+                continue;
+            }
+
+            let slot_start = origin + i as f64 * slot;
+            let gap = (options.gap_px as f64).min(slot - 1.0).max(0.0);
+            let start = slot_start + (gap / 2.0).floor();
+            let end = (slot_start + slot - 1.0 - (gap / 2.0).ceil()).max(start);
+
+            match options.layout {
+                BarLayout::Grouped => {
+                    let n = values.len() as f64;
+                    let inner = end - start + 1.0;
+                    let series_gap = (options.series_gap_px as f64)
+                        .min(((inner - n) / (n - 1.0).max(1.0)).max(0.0));
+                    let bar = ((inner - series_gap * (n - 1.0)) / n).max(1.0);
+
+                    for (j, &(value, color)) in values.iter().enumerate() {
+                        if !value.is_finite() {
+                            continue;
+                        }
+                        let b_start = (start + j as f64 * (bar + series_gap)).round();
+                        let b_end = (start + (j + 1) as f64 * bar + j as f64 * series_gap - 1.0)
+                            .round()
+                            .max(b_start);
+                        segments.push(BarSegment {
+                            start: b_start,
+                            end: b_end.min(end),
+                            from: baseline,
+                            to: value,
+                            color,
+                            label: Some(value),
+                            series: j,
+                        });
+                    }
+                }
+                BarLayout::Stacked => {
+                    let mut pos_top = baseline;
+                    let mut neg_top = baseline;
+                    let first_segment = segments.len();
+
+                    for (j, &(value, color)) in values.iter().enumerate() {
+                        if !value.is_finite() || value == 0.0 {
+                            continue;
+                        }
+                        let cursor = if value > 0.0 {
+                            &mut pos_top
+                        } else {
+                            &mut neg_top
+                        };
+                        let from = *cursor;
+                        *cursor += value;
+                        segments.push(BarSegment {
+                            start,
+                            end,
+                            from,
+                            to: *cursor,
+                            color,
+                            label: None,
+                            series: j,
+                        });
+                    }
+
+                    // Solo se etiqueta el total, en el segmento más externo de cada sentido.
+                    for (total, positive) in [(pos_top, true), (neg_top, false)] {
+                        if let Some(seg) = segments[first_segment..]
+                            .iter_mut()
+                            .rev()
+                            .find(|seg| (seg.to > seg.from) == positive)
+                        {
+                            seg.label = Some(total - baseline);
+                        }
+                    }
+                }
+            }
+        }
+
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BarChartOptions, BarLabels};
+    use crate::canvas::FillPattern;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn negative_bars_grow_down_from_zero_baseline() {
+        let mut chart = ChartContext::new(4, 2);
+        let options = BarChartOptions {
+            gap_px: 0,
+            ..BarChartOptions::default()
+        };
+        chart.bar_chart_with(&[vec![(1.0, None)], vec![(-1.0, None)]], &options);
+
+        assert_eq!(chart.canvas.render_no_color(), "⣿⣿⣀⣀\n⠀⠀⣿⣿\n");
+    }
+
+    #[test]
+    fn stacked_bars_reach_the_sum_of_their_series() {
+        let mut stacked = ChartContext::new(2, 2);
+        let options = BarChartOptions {
+            show_baseline: false,
+            ..BarChartOptions::stacked().with_gap(0)
+        };
+        stacked.bar_chart_with(
+            &[vec![(1.0, None), (1.0, None)], vec![(1.0, None)]],
+            &options,
+        );
+
+        assert_eq!(stacked.canvas.render_no_color(), "⣿⠀\n⣿⣿\n");
+    }
+
+    #[test]
+    fn horizontal_bars_start_from_the_top_and_label_above() {
+        let mut chart = ChartContext::new(8, 2);
+        let options = BarChartOptions {
+            show_baseline: false,
+            ..BarChartOptions::default()
+                .horizontal()
+                .with_gap(0)
+                .with_labels(BarLabels::Above)
+        };
+        chart.bar_chart_with(&[vec![(2.0, None)], vec![(4.0, None)]], &options);
+
+        let rendered = chart.canvas.render_with_options(false, None);
+        let rows: Vec<&str> = rendered.lines().collect();
+        assert!(rows[0].starts_with("⣿⣿⣿⣿2.0"));
+        // Sin hueco a la derecha, la etiqueta de la barra larga va dentro.
+        assert_eq!(rows[1], "⣿⣿⣿⣿⣿4.0");
+    }
+
+    #[test]
+    fn stacked_series_can_be_told_apart_by_pattern() {
+        let mut chart = ChartContext::new(2, 2);
+        let options = BarChartOptions {
+            show_baseline: false,
+            ..BarChartOptions::stacked()
+                .with_gap(0)
+                .with_patterns(&[FillPattern::Solid, FillPattern::Checkerboard])
+        };
+        chart.bar_chart_with(&[vec![(1.0, None), (1.0, None)]], &options);
+
+        assert_eq!(chart.canvas.render_no_color(), "⢕⢕\n⣿⣿\n");
+    }
+}
@@ -0,0 +1,316 @@
+use super::ChartContext;
+use crate::canvas::BrailleCanvas;
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+
+impl ChartContext {
+    /// Curvas de nivel de `func` sobre `x_range` x `y_range`. Cada nivel es
+    /// `(valor, color)`; con `labels` se escribe el valor sobre su curva.
+    pub fn contour<F>(
+        &mut self,
+        func: F,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        levels: &[(f64, Option<Color>)],
+        labels: bool,
+    ) where
+        F: Fn(f64, f64) -> f64,
+    {
+        let Some(field) = self.sample_field(x_range, y_range, |x, y, _, _| func(x, y)) else {
+            return;
+        };
+        self.draw_contours(&field, levels, labels);
+    }
+
+    /// Igual que `contour` pero a partir de una rejilla de valores: `grid[j][i]` es
+    /// el valor en la columna `i` y fila `j`, con la fila 0 en `y_range.0` y las
+    /// demás repartidas uniformemente. Se interpola bilinealmente a la resolución
+    /// de sub-píxel antes de extraer las curvas.
+    pub fn contour_grid<R>(
+        &mut self,
+        grid: &[R],
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        levels: &[(f64, Option<Color>)],
+        labels: bool,
+    ) where
+        R: AsRef<[f64]>,
+    {
+        let rows = grid.len();
+        let cols = grid.iter().map(|row| row.as_ref().len()).min().unwrap_or(0);
+        if rows < 2 || cols < 2 {
+            return;
+        }
+        let value = |i: usize, j: usize| grid[j].as_ref()[i];
+        let Some(field) = self.sample_field(x_range, y_range, |_, _, u, v| {
+            let gx = u * (cols - 1) as f64;
+            let gy = v * (rows - 1) as f64;
+            let i = (gx.floor() as usize).min(cols - 2);
+            let j = (gy.floor() as usize).min(rows - 2);
+            let (fx, fy) = (gx - i as f64, gy - j as f64);
+            let bottom = value(i, j) * (1.0 - fx) + value(i + 1, j) * fx;
+            let top = value(i, j + 1) * (1.0 - fx) + value(i + 1, j + 1) * fx;
+            bottom * (1.0 - fy) + top * fy
+        }) else {
+            return;
+        };
+        self.draw_contours(&field, levels, labels);
+    }
+
+    /// Muestrea `sample(x, y, u, v)` en cada esquina de sub-píxel del área de
+    /// dibujo (`u`, `v` en `0..=1` sobre el espacio ya transformado por la escala).
+    fn sample_field<S>(
+        &self,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        sample: S,
+    ) -> Option<ScalarField>
+    where
+        S: Fn(f64, f64, f64, f64) -> f64,
+    {
+        let (x0, x1) = self.x_scale.transformed_range(x_range)?;
+        let (y0, y1) = self.y_scale.transformed_range(y_range)?;
+        let projection = self.projection(x_range, y_range);
+        let (left, bottom) = projection.map(x_range.0, y_range.0)?;
+        let (right, top) = projection.map(x_range.1, y_range.1)?;
+        let cols = (right - left).unsigned_abs() + 1;
+        let rows = (top - bottom).unsigned_abs() + 1;
+        if cols < 2 || rows < 2 {
+            return None;
+        }
+
+        let mut values = Vec::with_capacity(cols * rows);
+        for j in 0..rows {
+            let v = j as f64 / (rows - 1) as f64;
+            let y = self.y_scale.inverse_transform(y0 + v * (y1 - y0));
+            for i in 0..cols {
+                let u = i as f64 / (cols - 1) as f64;
+                let x = self.x_scale.inverse_transform(x0 + u * (x1 - x0));
+                values.push(sample(x, y, u, v));
+            }
+        }
+
+        Some(ScalarField {
+            cols,
+            rows,
+            origin: (left.min(right), bottom.min(top)),
+            values,
+        })
+    }
+
+    fn draw_contours(
+        &mut self,
+        field: &ScalarField,
+        levels: &[(f64, Option<Color>)],
+        labels: bool,
+    ) {
+        let mut candidates = Vec::new();
+
+        self.draw_foreground_overlay(|overlay| {
+            for &(level, color) in levels {
+                if !level.is_finite() {
+                    continue;
+                }
+                let segments = field.march(level, overlay, color);
+                candidates.push((level, color, segments));
+            }
+        });
+
+        if !labels {
+            return;
+        }
+
+        // Cada etiqueta va en el tramo más cercano al centro que no pise otra.
+        let center = (
+            field.origin.0 as f64 + field.cols as f64 / 2.0,
+            field.origin.1 as f64 + field.rows as f64 / 2.0,
+        );
+        let mut placed: Vec<(isize, isize, isize)> = Vec::new();
+        for (level, color, mut segments) in candidates {
+            let text = AxisScale::format_compact(level);
+            let len = text::display_width(&text) as isize;
+            segments.sort_by(|a, b| {
+                let da = (a.0 - center.0).powi(2) + (a.1 - center.1).powi(2);
+                let db = (b.0 - center.0).powi(2) + (b.1 - center.1).powi(2);
+                da.total_cmp(&db)
+            });
+
+            let spot = segments.iter().find_map(|&(px, py)| {
+                let col = (px / 2.0).round() as isize - len / 2;
+                let row = (py / 4.0).floor() as isize;
+                let fits = col >= 0 && (col + len) as usize <= self.canvas.width;
+                let free = placed
+                    .iter()
+                    .all(|&(r, c, l)| r != row || col > c + l || col + len < c);
+                (fits && free).then_some((row, col))
+            });
+            if let Some((row, col)) = spot {
+                self.text_at_cell(&text, col, row, color);
+                placed.push((row, col, len));
+            }
+        }
+    }
+}
+
+/// Valores muestreados en una rejilla alineada con los sub-píxeles; `origin` es
+/// el píxel cartesiano de la muestra `(0, 0)`.
+struct ScalarField {
+    cols: usize,
+    rows: usize,
+    origin: (isize, isize),
+    values: Vec<f64>,
+}
+
+impl ScalarField {
+    fn at(&self, i: usize, j: usize) -> f64 {
+        self.values[j * self.cols + i]
+    }
+
+    /// Marching squares para `level`: dibuja cada tramo y devuelve sus puntos medios
+    /// (en píxeles) para poder situar etiquetas.
+    fn march(
+        &self,
+        level: f64,
+        overlay: &mut BrailleCanvas,
+        color: Option<Color>,
+    ) -> Vec<(f64, f64)> {
+        let mut midpoints = Vec::new();
+        for j in 0..self.rows - 1 {
+            for i in 0..self.cols - 1 {
+                let corners = [
+                    self.at(i, j),
+                    self.at(i + 1, j),
+                    self.at(i + 1, j + 1),
+                    self.at(i, j + 1),
+                ];
+                if corners.iter().any(|v| !v.is_finite()) {
+                    continue;
+                }
+                let case = corners
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |acc, (bit, v)| acc | (u8::from(*v >= level) << bit));
+                if case == 0 || case == 15 {
+                    continue;
+                }
+
+                // Aristas: 0 abajo, 1 derecha, 2 arriba, 3 izquierda.
+                let crossing = |edge: usize| -> (f64, f64) {
+                    let (a, b, from, to) = match edge {
+                        0 => (corners[0], corners[1], (0.0, 0.0), (1.0, 0.0)),
+                        1 => (corners[1], corners[2], (1.0, 0.0), (1.0, 1.0)),
+                        2 => (corners[3], corners[2], (0.0, 1.0), (1.0, 1.0)),
+                        _ => (corners[0], corners[3], (0.0, 0.0), (0.0, 1.0)),
+                    };
+                    let t = if (b - a).abs() < 1e-12 {
+                        0.5
+                    } else {
+                        ((level - a) / (b - a)).clamp(0.0, 1.0)
+                    };
+                    (
+                        self.origin.0 as f64 + i as f64 + from.0 + (to.0 - from.0) * t,
+                        self.origin.1 as f64 + j as f64 + from.1 + (to.1 - from.1) * t,
+                    )
+                };
+
+                let center_above = corners.iter().sum::<f64>() / 4.0 >= level;
+                let pairs: &[(usize, usize)] = match case {
+                    1 | 14 => &[(3, 0)],
+                    2 | 13 => &[(0, 1)],
+                    3 | 12 => &[(3, 1)],
+                    4 | 11 => &[(1, 2)],
+                    6 | 9 => &[(0, 2)],
+                    7 | 8 => &[(3, 2)],
+                    // Puntos de silla: el valor del centro decide cómo se conectan.
+                    5 if center_above => &[(3, 2), (0, 1)],
+                    5 => &[(3, 0), (1, 2)],
+                    10 if center_above => &[(3, 0), (1, 2)],
+                    _ => &[(3, 2), (0, 1)],
+                };
+
+                for &(e0, e1) in pairs {
+                    let (a, b) = (crossing(e0), crossing(e1));
+                    overlay.line(
+                        a.0.round() as isize,
+                        a.1.round() as isize,
+                        b.0.round() as isize,
+                        b.1.round() as isize,
+                        color,
+                    );
+                    midpoints.push(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0));
+                }
+            }
+        }
+        midpoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn circular_level_set_renders_a_closed_ring() {
+        let mut chart = ChartContext::new(8, 4);
+        chart.contour(
+            |x, y| (x * x + y * y).sqrt(),
+            (-1.0, 1.0),
+            (-1.0, 1.0),
+            &[(0.8, None)],
+            false,
+        );
+
+        let rendered = chart.canvas.render_no_color();
+        let rows: Vec<&str> = rendered.lines().collect();
+        // Sin píxeles en las esquinas (fuera del anillo) ni en el centro.
+        assert!(rows[0].starts_with('⠀'));
+        assert!(rows[3].ends_with('⠀'));
+        assert_eq!(rows[1].chars().nth(3), Some('⠀'));
+        assert_ne!(
+            rendered.chars().filter(|c| *c != '⠀' && *c != '\n').count(),
+            0
+        );
+    }
+
+    #[test]
+    fn grid_and_function_contours_agree_for_linear_fields() {
+        let mut from_fn = ChartContext::new(6, 3);
+        from_fn.contour(|x, _| x, (0.0, 1.0), (0.0, 1.0), &[(0.5, None)], false);
+
+        let mut from_grid = ChartContext::new(6, 3);
+        from_grid.contour_grid(
+            &[[0.0, 1.0], [0.0, 1.0]],
+            (0.0, 1.0),
+            (0.0, 1.0),
+            &[(0.5, None)],
+            false,
+        );
+
+        assert_eq!(
+            from_fn.canvas.render_no_color(),
+            from_grid.canvas.render_no_color()
+        );
+        assert_eq!(from_fn.canvas.render_no_color(), "⠀⠀⠀⡇⠀⠀\n⠀⠀⠀⡇⠀⠀\n⠀⠀⠀⡇⠀⠀\n");
+    }
+
+    #[test]
+    fn level_labels_are_written_on_the_curve() {
+        let mut chart = ChartContext::new(12, 4);
+        chart.contour(|x, _| x, (0.0, 1.0), (0.0, 1.0), &[(0.5, None)], true);
+
+        assert!(chart
+            .canvas
+            .render_with_options(false, None)
+            .contains("0.5"));
+
+        // Los niveles son valores de z: no se formatean con la escala del eje y,
+        // que con un decimal mostraría `0.2`.
+        let mut chart = ChartContext::new(12, 4);
+        chart.contour(|x, _| x, (0.0, 1.0), (0.0, 1.0), &[(0.25, None)], true);
+        assert!(chart
+            .canvas
+            .render_with_options(false, None)
+            .contains("0.25"));
+    }
+}
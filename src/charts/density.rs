@@ -0,0 +1,300 @@
+use super::{ChartContext, LineInterpolation};
+use crate::scale::AxisScale;
+use crate::stats::{self, Bandwidth};
+use colored::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViolinOptions {
+    pub bandwidth: Bandwidth,
+    pub filled: bool,
+    /// Marca la mediana con una línea horizontal del ancho del violín.
+    pub show_median: bool,
+    /// Fracción del hueco de cada categoría que ocupa el violín más ancho.
+    pub width: f64,
+}
+
+impl Default for ViolinOptions {
+    fn default() -> Self {
+        Self {
+            bandwidth: Bandwidth::Silverman,
+            filled: false,
+            show_median: true,
+            width: 0.9,
+        }
+    }
+}
+
+impl ChartContext {
+    /// Curva de densidad (núcleo gaussiano) de `samples`. Se calcula sobre el eje
+    /// `x` ya transformado, de modo que en escala log se estima la densidad de
+    /// `log10(x)`.
+    pub fn kde(&mut self, samples: &[f64], bandwidth: Bandwidth, color: Option<Color>) {
+        let x_scale = self.x_scale;
+        let transformed: Vec<f64> = samples
+            .iter()
+            .filter_map(|v| x_scale.transform(*v))
+            .collect();
+        if transformed.is_empty() {
+            return;
+        }
+        let h = bandwidth.resolve(&transformed);
+        let (min, max) = transformed
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(*v), hi.max(*v))
+            });
+        let (start, end) = (min - 3.0 * h, max + 3.0 * h);
+
+        let steps = self.canvas.pixel_width().saturating_sub(1).max(1);
+        let points: Vec<(f64, f64)> = (0..=steps)
+            .map(|i| {
+                let t = start + (end - start) * i as f64 / steps as f64;
+                (
+                    x_scale.inverse_transform(t),
+                    stats::gaussian_kde(&transformed, h, t),
+                )
+            })
+            .collect();
+
+        // La densidad siempre parte de cero; solo se auto-escala el máximo.
+        let (x_range, y_range) = self.view_range.unwrap_or_else(|| {
+            let peak = points.iter().map(|(_, d)| *d).fold(0.0, f64::max);
+            let y_range = match self.y_scale {
+                AxisScale::Linear => (0.0, peak * 1.05),
+                AxisScale::Log10 => {
+                    Self::get_auto_range_scaled(&points, 0.05, x_scale, self.y_scale).1
+                }
+            };
+            (
+                (
+                    x_scale.inverse_transform(start),
+                    x_scale.inverse_transform(end),
+                ),
+                y_range,
+            )
+        });
+        self.line_chart_with_ranges(&points, x_range, y_range, LineInterpolation::Linear, color);
+    }
+
+    /// Violines verticales, uno por grupo `(muestras, color)`, repartidos a lo
+    /// ancho. Todos comparten el eje de valores (`y_scale`) y el ancho se
+    /// normaliza con la densidad máxima de todos los grupos.
+    pub fn violin_plot(&mut self, groups: &[(&[f64], Option<Color>)], options: &ViolinOptions) {
+        if groups.is_empty() {
+            return;
+        }
+        let y_scale = self.y_scale;
+        let transformed: Vec<Vec<f64>> = groups
+            .iter()
+            .map(|(samples, _)| {
+                samples
+                    .iter()
+                    .filter_map(|v| y_scale.transform(*v))
+                    .collect()
+            })
+            .collect();
+        let bandwidths: Vec<f64> = transformed
+            .iter()
+            .map(|t| {
+                if t.is_empty() {
+                    0.0
+                } else {
+                    options.bandwidth.resolve(t)
+                }
+            })
+            .collect();
+
+        // Rango de valores: datos +- 2h para que las colas no se corten en seco.
+        let extents: Vec<(f64, f64)> = transformed
+            .iter()
+            .zip(&bandwidths)
+            .flat_map(|(t, h)| {
+                t.iter()
+                    .map(move |v| [(0.0, v - 2.0 * h), (0.0, v + 2.0 * h)])
+            })
+            .flatten()
+            .map(|(x, t)| (x, y_scale.inverse_transform(t)))
+            .collect();
+        if extents.is_empty() {
+            return;
+        }
+        let (_, y_range) = self.resolve_ranges(&extents);
+        let Some((t_min, t_max)) = y_scale.transformed_range(y_range) else {
+            return;
+        };
+
+        let projection = self.projection((0.0, 1.0), y_range);
+        let left = projection.left_inset_px as f64;
+        let bottom = projection.bottom_inset_px as isize;
+        let drawable_w = projection.width_px.saturating_sub(projection.left_inset_px) as f64;
+        let drawable_h = projection
+            .height_px
+            .saturating_sub(1 + projection.bottom_inset_px)
+            .max(1);
+        let slot = drawable_w / groups.len() as f64;
+        let max_half = (slot * options.width.clamp(0.0, 1.0) / 2.0 - 0.5).max(0.0);
+
+        // Densidad de cada grupo en cada fila de píxeles.
+        let rows: Vec<Vec<f64>> = transformed
+            .iter()
+            .zip(&bandwidths)
+            .map(|(t, &h)| {
+                let (lo, hi) = t
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| {
+                        (a.min(*v), b.max(*v))
+                    });
+                (0..=drawable_h)
+                    .map(|r| {
+                        let value = t_min + (t_max - t_min) * r as f64 / drawable_h as f64;
+                        if t.is_empty() || value < lo - 2.0 * h || value > hi + 2.0 * h {
+                            0.0
+                        } else {
+                            stats::gaussian_kde(t, h, value)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let peak = rows.iter().flatten().copied().fold(0.0, f64::max);
+        if peak <= 0.0 {
+            return;
+        }
+
+        let medians: Vec<Option<isize>> = transformed
+            .iter()
+            .map(|t| {
+                let sorted = stats::sorted_finite(t);
+                let m = stats::quantile(&sorted, 0.5);
+                m.is_finite().then(|| {
+                    bottom
+                        + ((m - t_min) / (t_max - t_min).max(1e-9) * drawable_h as f64).round()
+                            as isize
+                })
+            })
+            .collect();
+
+        self.draw_foreground_overlay(|overlay| {
+            for (g, densities) in rows.iter().enumerate() {
+                let color = groups[g].1;
+                // Centro en coordenadas de píxel; redondeo simétrico a ambos lados.
+                let center = left + slot * (g as f64 + 0.5) - 0.5;
+                let half_at = |d: f64| (d / peak * max_half).max(0.5);
+                let span = |half: f64| {
+                    (
+                        (center - half + 0.5).floor() as isize,
+                        (center + half - 0.5).ceil() as isize,
+                    )
+                };
+                let mut prev: Option<(isize, isize, isize)> = None;
+
+                for (r, &d) in densities.iter().enumerate() {
+                    let y = bottom + r as isize;
+                    if d <= 0.0 {
+                        prev = None;
+                        continue;
+                    }
+                    let (l, rr) = span(half_at(d));
+                    if options.filled {
+                        overlay.fill_span(l, rr, y, color, true);
+                    } else {
+                        match prev {
+                            Some((py, pl, pr)) => {
+                                overlay.line(pl, py, l, y, color);
+                                overlay.line(pr, py, rr, y, color);
+                            }
+                            None => overlay.line(l, y, rr, y, color),
+                        }
+                    }
+                    prev = Some((y, l, rr));
+                }
+                // Cierra el contorno por arriba.
+                if let (Some((y, l, r)), false) = (prev, options.filled) {
+                    overlay.line(l, y, r, y, color);
+                }
+
+                if let (true, Some(y)) = (options.show_median, medians[g]) {
+                    let r = (y - bottom).clamp(0, densities.len() as isize - 1) as usize;
+                    let half = half_at(densities[r]).max(1.0);
+                    let (l, rr) = (
+                        (center - half).round() as isize,
+                        (center + half).round() as isize,
+                    );
+                    if options.filled {
+                        // Sobre un violín relleno la mediana se "recorta" en negativo.
+                        for x in l..=rr {
+                            if x >= 0 && y >= 0 {
+                                overlay.unset_pixel(x as usize, y as usize);
+                            }
+                        }
+                    } else {
+                        overlay.line(l, y, rr, y, color);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ViolinOptions;
+    use crate::charts::ChartContext;
+    use crate::stats::Bandwidth;
+
+    #[test]
+    fn kde_peaks_over_the_sample_cluster() {
+        let mut chart = ChartContext::new(20, 4);
+        chart.kde(&[5.0, 5.1, 4.9, 5.0], Bandwidth::Fixed(0.5), None);
+
+        let rendered = chart.canvas.render_no_color();
+        let top = rendered.lines().next().unwrap();
+        let lit: Vec<usize> = top
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| *c != '⠀')
+            .map(|(i, _)| i)
+            .collect();
+        // Simétrica y centrada: el pico cae en las columnas centrales.
+        assert!(!lit.is_empty());
+        assert!(lit.iter().all(|&i| (8..=11).contains(&i)), "{lit:?}");
+    }
+
+    #[test]
+    fn violins_are_mirrored_and_placed_per_category() {
+        let mut chart = ChartContext::new(12, 4);
+        let a = [1.0, 2.0, 2.0, 3.0];
+        let b = [10.0, 11.0, 11.0, 12.0];
+        chart.violin_plot(
+            &[(&a, None), (&b, None)],
+            &ViolinOptions {
+                filled: true,
+                show_median: false,
+                ..ViolinOptions::default()
+            },
+        );
+
+        let rendered = chart.canvas.render_no_color();
+        let rows: Vec<&str> = rendered.lines().collect();
+        // El grupo alto ocupa arriba a la derecha y el bajo abajo a la izquierda.
+        assert!(rows[0].chars().take(6).all(|c| c == '⠀'));
+        assert!(rows[0].chars().skip(6).any(|c| c != '⠀'));
+        assert!(rows[3].chars().take(6).any(|c| c != '⠀'));
+        for row in rows {
+            let cells: Vec<char> = row.chars().collect();
+            let left: String = cells[..6].iter().collect();
+            let mirrored: String = cells[..6].iter().rev().map(|c| mirror(*c)).collect();
+            assert_eq!(left, mirrored);
+        }
+    }
+
+    fn mirror(c: char) -> char {
+        let mask = c as u32 - 0x2800;
+        let swap = |m: u32, a: u32, b: u32| {
+            let (bit_a, bit_b) = ((m >> a) & 1, (m >> b) & 1);
+            (m & !(1 << a) & !(1 << b)) | (bit_a << b) | (bit_b << a)
+        };
+        let m = swap(swap(swap(swap(mask, 0, 3), 1, 4), 2, 5), 6, 7);
+        char::from_u32(0x2800 + m).unwrap()
+    }
+}
@@ -0,0 +1,194 @@
+use super::{ChartContext, LineInterpolation};
+use crate::stats;
+use colored::Color;
+
+/// Distribución contra la que se comparan los cuantiles de un Q-Q plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QqReference<'a> {
+    /// Normal con la media y desviación típica de la propia muestra.
+    Normal,
+    Sample(&'a [f64]),
+}
+
+impl ChartContext {
+    /// Función de distribución empírica en escalera. Cada valor de
+    /// `percentiles` (0..=1, p. ej. `[0.5, 0.9, 0.99]`) se marca con una línea
+    /// punteada vertical hasta la curva y una etiqueta `pNN`.
+    pub fn ecdf(&mut self, samples: &[f64], percentiles: &[f64], color: Option<Color>) {
+        let sorted = stats::sorted_finite(samples);
+        if sorted.is_empty() {
+            return;
+        }
+        let n = sorted.len() as f64;
+        let mut points = Vec::with_capacity(sorted.len() + 3);
+        points.push((sorted[0], 0.0));
+        points.extend(
+            sorted
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| (x, (i + 1) as f64 / n)),
+        );
+
+        let (x_range, y_range) = self.resolve_ranges(&points);
+        // Las colas llegan hasta los bordes del rango visible.
+        points.insert(0, (x_range.0, 0.0));
+        points.push((x_range.1, 1.0));
+        self.line_chart_with_ranges(
+            &points,
+            x_range,
+            y_range,
+            LineInterpolation::StepPost,
+            color,
+        );
+
+        let projection = self.projection(x_range, y_range);
+        let markers: Vec<(String, isize, isize, isize)> = percentiles
+            .iter()
+            .filter(|p| (0.0..=1.0).contains(*p))
+            .filter_map(|&p| {
+                let value = stats::nearest_rank(&sorted, p);
+                let (x, top) = projection.map(value, p)?;
+                let (_, bottom) = projection.map(value, y_range.0)?;
+                let label = format!("p{}", (p * 100_000.0).round() / 1000.0);
+                Some((label, x, bottom, top))
+            })
+            .collect();
+
+        self.draw_foreground_overlay(|overlay| {
+            for (_, x, bottom, top) in &markers {
+                for y in (*bottom..=*top).step_by(2) {
+                    if *x >= 0 && y >= 0 {
+                        overlay.set_pixel(*x as usize, y as usize, color);
+                    }
+                }
+            }
+        });
+        // Etiqueta bajo el escalón y a la derecha de la marca: la curva es
+        // creciente, así que esa zona queda libre.
+        for (label, x, _, y) in &markers {
+            self.text_at_cell(label, x / 2 + 1, y / 4 - 1, color);
+        }
+    }
+
+    /// Q-Q plot: cuantiles de `samples` (eje y) frente a los de `reference`
+    /// (eje x), con la diagonal `y = x` como referencia. Ambos ejes comparten
+    /// rango para que la diagonal conserve su significado.
+    pub fn qq_plot(
+        &mut self,
+        samples: &[f64],
+        reference: QqReference,
+        color: Option<Color>,
+        diagonal_color: Option<Color>,
+    ) {
+        let sorted = stats::sorted_finite(samples);
+        let points: Vec<(f64, f64)> = match reference {
+            QqReference::Normal => {
+                let (mean, sd) = (stats::mean(&sorted), stats::std_dev(&sorted));
+                let n = sorted.len() as f64;
+                sorted
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &y)| (mean + sd * stats::normal_quantile((i as f64 + 0.5) / n), y))
+                    .collect()
+            }
+            QqReference::Sample(other) => {
+                let other = stats::sorted_finite(other);
+                let m = sorted.len().min(other.len());
+                (0..m)
+                    .map(|i| {
+                        let p = (i as f64 + 0.5) / m as f64;
+                        (stats::quantile(&other, p), stats::quantile(&sorted, p))
+                    })
+                    .collect()
+            }
+        };
+        if points.is_empty() {
+            return;
+        }
+
+        let (lo, hi) = points
+            .iter()
+            .flat_map(|&(x, y)| [x, y])
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| {
+                (a.min(v), b.max(v))
+            });
+        let (x_range, y_range) = self.view_range.unwrap_or_else(|| {
+            let (x_range, y_range) = Self::get_auto_range_scaled(
+                &[(lo, lo), (hi, hi)],
+                0.05,
+                self.x_scale,
+                self.y_scale,
+            );
+            let shared = (x_range.0.min(y_range.0), x_range.1.max(y_range.1));
+            (shared, shared)
+        });
+
+        // Diagonal muestreada en el espacio transformado de x, para que siga
+        // siendo correcta aunque las escalas de los ejes difieran.
+        let diagonal: Vec<(f64, f64)> = self
+            .x_scale
+            .transformed_range(x_range)
+            .map(|(t0, t1)| {
+                (0..=32)
+                    .map(|i| {
+                        let x = self
+                            .x_scale
+                            .inverse_transform(t0 + (t1 - t0) * i as f64 / 32.0);
+                        (x, x)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let projection = self.projection(x_range, y_range);
+        self.draw_background_overlay(|overlay| {
+            let mapped: Vec<(isize, isize)> = diagonal
+                .iter()
+                .filter_map(|&(x, y)| projection.map(x, y))
+                .collect();
+            for w in mapped.windows(2) {
+                overlay.line(w[0].0, w[0].1, w[1].0, w[1].1, diagonal_color);
+            }
+        });
+        self.scatter_with_ranges(&points, x_range, y_range, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QqReference;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn ecdf_climbs_in_steps_and_labels_percentiles() {
+        let mut chart = ChartContext::new(12, 4);
+        chart.set_view_range((0.0, 4.0), (0.0, 1.0));
+        chart.ecdf(&[1.0, 2.0, 3.0, 4.0], &[0.5], None);
+
+        assert_eq!(
+            chart.canvas.render_no_color(),
+            "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢸\n⠀⠀⠀⠀⠀⠀⣀⣀⣸⠉⠉⠉\n⠀⠀⠀⣀⣀⣀⡇⠀⠀⠀⠀⠀\n⣀⣀⣀⡇⠀⠀⡂⠀⠀⠀⠀⠀\n"
+        );
+        let rendered = chart.canvas.render_with_options(false, None);
+        assert_eq!(rendered.lines().nth(2), Some("⠀⠀⠀⣀⣀⣀⡇p50⠀⠀"));
+    }
+
+    #[test]
+    fn identical_samples_fall_on_the_diagonal() {
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut with_sample = ChartContext::new(10, 5);
+        with_sample.qq_plot(&samples, QqReference::Sample(&samples), None, None);
+        let rendered = with_sample.canvas.render_no_color();
+        // Todos los puntos caen sobre la diagonal: no hay píxeles fuera de ella.
+        let lit_rows: Vec<usize> = rendered
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| l.chars().any(|c| c != '⠀'))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(lit_rows, vec![0, 1, 2, 3, 4]);
+        for (row, line) in rendered.lines().enumerate() {
+            let first = line.chars().position(|c| c != '⠀').unwrap();
+            assert_eq!(first, 8 - 2 * row);
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use super::{ChartContext, LineInterpolation, Projection};
+use crate::canvas::{BrailleCanvas, FillPattern};
+use colored::Color;
+
+/// Incertidumbre de un punto como distancias (no negativas) hacia cada lado.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ErrorBar {
+    pub x_low: f64,
+    pub x_high: f64,
+    pub y_low: f64,
+    pub y_high: f64,
+}
+
+impl ErrorBar {
+    pub fn y(err: f64) -> Self {
+        Self::y_asymmetric(err, err)
+    }
+
+    pub fn y_asymmetric(low: f64, high: f64) -> Self {
+        Self {
+            y_low: low,
+            y_high: high,
+            ..Self::default()
+        }
+    }
+
+    pub fn x(err: f64) -> Self {
+        Self::x_asymmetric(err, err)
+    }
+
+    pub fn x_asymmetric(low: f64, high: f64) -> Self {
+        Self {
+            x_low: low,
+            x_high: high,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_x(mut self, low: f64, high: f64) -> Self {
+        self.x_low = low;
+        self.x_high = high;
+        self
+    }
+
+    pub fn with_y(mut self, low: f64, high: f64) -> Self {
+        self.y_low = low;
+        self.y_high = high;
+        self
+    }
+
+    /// Extremos en unidades de datos: `(x_min, x_max, y_min, y_max)`.
+    fn bounds(&self, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        let d = |v: f64| if v.is_finite() { v.abs() } else { 0.0 };
+        (
+            x - d(self.x_low),
+            x + d(self.x_high),
+            y - d(self.y_low),
+            y + d(self.y_high),
+        )
+    }
+}
+
+impl ChartContext {
+    /// Scatter con barras de error; `errors[i]` corresponde a `points[i]` y los
+    /// puntos sin error asociado se dibujan sin barra. `cap_px` es el semiancho
+    /// de los remates (0 = sin remate).
+    pub fn scatter_with_errors(
+        &mut self,
+        points: &[(f64, f64)],
+        errors: &[ErrorBar],
+        cap_px: usize,
+        color: Option<Color>,
+    ) {
+        if points.is_empty() {
+            return;
+        }
+        let (x_range, y_range) = self.error_ranges(points, errors);
+        self.error_bars_with_ranges(points, errors, cap_px, x_range, y_range, color);
+        self.scatter_with_ranges(points, x_range, y_range, color);
+    }
+
+    /// Igual que `scatter_with_errors` pero uniendo los puntos con líneas.
+    pub fn line_chart_with_errors(
+        &mut self,
+        points: &[(f64, f64)],
+        errors: &[ErrorBar],
+        cap_px: usize,
+        color: Option<Color>,
+    ) {
+        if points.len() < 2 {
+            return;
+        }
+        let (x_range, y_range) = self.error_ranges(points, errors);
+        self.error_bars_with_ranges(points, errors, cap_px, x_range, y_range, color);
+        self.line_chart_with_ranges(points, x_range, y_range, LineInterpolation::Linear, color);
+    }
+
+    /// Banda sombreada entre `low` y `high` para cada `(x, low, high)`, interpolada
+    /// linealmente en píxeles entre muestras consecutivas. Se rellena con el
+    /// `fill_pattern` del lienzo; con el `Solid` por defecto usa un damero para que
+    /// las líneas dibujadas encima sigan distinguiéndose.
+    pub fn confidence_band(&mut self, band: &[(f64, f64, f64)], color: Option<Color>) {
+        if band.len() < 2 {
+            return;
+        }
+        let bounds: Vec<(f64, f64)> = band
+            .iter()
+            .flat_map(|&(x, low, high)| [(x, low), (x, high)])
+            .collect();
+        let (x_range, y_range) = self.resolve_ranges(&bounds);
+        let projection = self.projection(x_range, y_range);
+
+        self.draw_foreground_overlay(|overlay| {
+            if overlay.fill_pattern == FillPattern::Solid {
+                overlay.fill_pattern = FillPattern::Checkerboard;
+            }
+            for window in band.windows(2) {
+                let (x0, lo0, hi0) = window[0];
+                let (x1, lo1, hi1) = window[1];
+                let (Some(a_lo), Some(a_hi), Some(b_lo), Some(b_hi)) = (
+                    projection.map(x0, lo0),
+                    projection.map(x0, hi0),
+                    projection.map(x1, lo1),
+                    projection.map(x1, hi1),
+                ) else {
+                    continue;
+                };
+
+                // Límites de cada columna y, por filas, tramos de columnas seguidas
+                // que caen dentro de la banda.
+                let (start, end) = (a_lo.0.min(b_lo.0), a_lo.0.max(b_lo.0));
+                let columns: Vec<(isize, isize, isize)> = (start..=end)
+                    .map(|px| {
+                        let t = if end == start {
+                            0.0
+                        } else {
+                            (px - a_lo.0) as f64 / (b_lo.0 - a_lo.0) as f64
+                        };
+                        let lerp =
+                            |a: isize, b: isize| (a as f64 + (b - a) as f64 * t).round() as isize;
+                        let (lo, hi) = (lerp(a_lo.1, b_lo.1), lerp(a_hi.1, b_hi.1));
+                        (px, lo.min(hi), lo.max(hi))
+                    })
+                    .collect();
+                let bottom = columns.iter().map(|c| c.1).min().unwrap_or(0);
+                let top = columns.iter().map(|c| c.2).max().unwrap_or(-1);
+                for py in bottom..=top {
+                    let mut run: Option<(isize, isize)> = None;
+                    for &(px, lo, hi) in &columns {
+                        if (lo..=hi).contains(&py) {
+                            run = Some((run.map_or(px, |r| r.0), px));
+                        } else if let Some((x0, x1)) = run.take() {
+                            overlay.fill_span(x0, x1, py, color, true);
+                        }
+                    }
+                    if let Some((x0, x1)) = run {
+                        overlay.fill_span(x0, x1, py, color, true);
+                    }
+                }
+            }
+        });
+    }
+
+    fn error_ranges(&self, points: &[(f64, f64)], errors: &[ErrorBar]) -> ((f64, f64), (f64, f64)) {
+        let mut extents = points.to_vec();
+        for (&(x, y), err) in points.iter().zip(errors) {
+            let (x_min, x_max, y_min, y_max) = err.bounds(x, y);
+            extents.push((x_min, y_min));
+            extents.push((x_max, y_max));
+        }
+        self.resolve_ranges(&extents)
+    }
+
+    fn error_bars_with_ranges(
+        &mut self,
+        points: &[(f64, f64)],
+        errors: &[ErrorBar],
+        cap_px: usize,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        color: Option<Color>,
+    ) {
+        let projection = self.projection(x_range, y_range);
+
+        self.draw_foreground_overlay(|overlay| {
+            for (&(x, y), err) in points.iter().zip(errors) {
+                let Some(center) = projection.map(x, y) else {
+                    continue;
+                };
+                let (x_min, x_max, y_min, y_max) = err.bounds(x, y);
+
+                if y_min < y || y_max > y {
+                    let low = Self::error_end(&projection, (x, y_min), center, false);
+                    let high = Self::error_end(&projection, (x, y_max), center, false);
+                    overlay.line(center.0, low.0, center.0, high.0, color);
+                    for (end, capped) in [low, high] {
+                        if capped {
+                            Self::error_cap(overlay, center.0, end, cap_px, true, color);
+                        }
+                    }
+                }
+
+                if x_min < x || x_max > x {
+                    let low = Self::error_end(&projection, (x_min, y), center, true);
+                    let high = Self::error_end(&projection, (x_max, y), center, true);
+                    overlay.line(low.0, center.1, high.0, center.1, color);
+                    for (end, capped) in [low, high] {
+                        if capped {
+                            Self::error_cap(overlay, end, center.1, cap_px, false, color);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Coordenada en píxeles del extremo de una barra y si lleva remate. Un extremo
+    /// que la escala no puede representar (p. ej. `<= 0` en log) se lleva al borde
+    /// del gráfico sin remate, para no sugerir un límite que no existe.
+    fn error_end(
+        projection: &Projection,
+        end: (f64, f64),
+        center: (isize, isize),
+        horizontal: bool,
+    ) -> (isize, bool) {
+        let coord = |p: (isize, isize)| if horizontal { p.0 } else { p.1 };
+        if let Some(p) = projection.map(end.0, end.1) {
+            let capped = coord(p) != coord(center);
+            return (coord(p), capped);
+        }
+        let edge = if horizontal {
+            projection.left_inset_px as isize
+        } else {
+            projection.bottom_inset_px as isize
+        };
+        (edge, false)
+    }
+
+    fn error_cap(
+        overlay: &mut BrailleCanvas,
+        along: isize,
+        at: isize,
+        cap_px: usize,
+        horizontal_cap: bool,
+        color: Option<Color>,
+    ) {
+        if cap_px == 0 {
+            return;
+        }
+        let half = cap_px as isize;
+        if horizontal_cap {
+            overlay.line(along - half, at, along + half, at, color);
+        } else {
+            overlay.line(at, along - half, at, along + half, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorBar;
+    use crate::canvas::FillPattern;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn symmetric_y_error_draws_capped_vertical_bar() {
+        let mut chart = ChartContext::new(3, 3);
+        chart.set_view_range((0.0, 2.0), (0.0, 2.0));
+        chart.scatter_with_errors(&[(1.0, 1.0)], &[ErrorBar::y(1.0)], 1, None);
+
+        assert_eq!(chart.canvas.render_no_color(), "⠀⢹⠁\n⠀⢸⠀\n⠀⣸⡀\n");
+    }
+
+    #[test]
+    fn error_extents_widen_the_auto_range() {
+        let mut plain = ChartContext::new(10, 4);
+        plain.scatter(&[(0.0, 0.0), (1.0, 1.0)], None);
+        let mut with_errors = ChartContext::new(10, 4);
+        with_errors.scatter_with_errors(
+            &[(0.0, 0.0), (1.0, 1.0)],
+            &[ErrorBar::y(5.0), ErrorBar::default()],
+            0,
+            None,
+        );
+
+        // El segundo punto ya no toca el techo: el rango incluye y = 5.
+        let top_row = |chart: &ChartContext| {
+            chart
+                .canvas
+                .render_no_color()
+                .lines()
+                .next()
+                .map(str::to_string)
+        };
+        assert_ne!(top_row(&plain), top_row(&with_errors));
+    }
+
+    #[test]
+    fn confidence_band_shades_between_bounds() {
+        let mut chart = ChartContext::new(4, 2);
+        chart.set_view_range((0.0, 1.0), (0.0, 1.0));
+        chart.confidence_band(&[(0.0, 0.0, 1.0), (1.0, 0.0, 1.0)], None);
+
+        assert_eq!(chart.canvas.render_no_color(), "⢕⢕⢕⢕\n⢕⢕⢕⢕\n");
+
+        // Con una trama en el lienzo la banda la usa en lugar del damero.
+        let mut chart = ChartContext::new(4, 2);
+        chart.canvas.fill_pattern = FillPattern::HorizontalLines;
+        chart.set_view_range((0.0, 1.0), (0.0, 1.0));
+        chart.confidence_band(&[(0.0, 0.0, 0.5), (1.0, 0.0, 1.0)], None);
+        assert_eq!(chart.canvas.render_no_color(), "⠀⠀⠀⠉\n⠉⠉⠉⠉\n");
+    }
+}
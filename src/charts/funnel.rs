@@ -0,0 +1,130 @@
+use super::ChartContext;
+use crate::text;
+use colored::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FunnelOptions {
+    /// Escribe a la derecha la conversión de cada etapa.
+    pub show_percent: bool,
+    /// Conversión respecto a la primera etapa en vez de a la anterior.
+    pub relative_to_first: bool,
+    /// Píxeles libres bajo cada etapa.
+    pub gap_px: usize,
+    pub label_color: Option<Color>,
+}
+
+impl Default for FunnelOptions {
+    fn default() -> Self {
+        Self {
+            show_percent: true,
+            relative_to_first: false,
+            gap_px: 1,
+            label_color: None,
+        }
+    }
+}
+
+impl ChartContext {
+    /// Embudo: una banda por etapa `(etiqueta, valor, color)`, de arriba abajo,
+    /// con barras centradas de ancho proporcional al valor. Las etiquetas van a
+    /// la izquierda y los porcentajes de conversión a la derecha; las etapas que
+    /// no caben en la altura del lienzo se omiten.
+    pub fn funnel_chart(&mut self, stages: &[(&str, f64, Option<Color>)], options: &FunnelOptions) {
+        let width = self.canvas.width;
+        let height = self.canvas.height;
+        let max_value = stages
+            .iter()
+            .map(|s| s.1)
+            .filter(|v| v.is_finite())
+            .fold(0.0, f64::max);
+        if stages.is_empty() || max_value <= 0.0 || width < 2 {
+            return;
+        }
+
+        let rows_per_stage = (height / stages.len()).max(1);
+        let visible = stages.len().min(height / rows_per_stage);
+        let longest = stages
+            .iter()
+            .map(|s| text::display_width(s.0))
+            .max()
+            .unwrap_or(0);
+        let left = if longest == 0 {
+            0
+        } else {
+            longest.min(width / 3) + 1
+        };
+        let right = if options.show_percent { 5 } else { 0 };
+        if left + right >= width {
+            return;
+        }
+
+        let area_start = (left * 2) as f64;
+        let area_len = (width.saturating_sub(left + right) * 2).max(1) as f64;
+        // Centro en coordenadas de píxel; redondeo simétrico a ambos lados.
+        let center = area_start + area_len / 2.0 - 0.5;
+        let band_px = rows_per_stage * 4;
+        let bar_height = band_px.saturating_sub(options.gap_px).max(1);
+
+        self.draw_foreground_overlay(|overlay| {
+            for (i, &(_, value, color)) in stages.iter().take(visible).enumerate() {
+                if !value.is_finite() || value <= 0.0 {
+                    continue;
+                }
+                let half = (value / max_value * area_len / 2.0).max(0.5);
+                let x0 = (center - half + 0.5).floor() as isize;
+                let x1 = (center + half - 0.5).ceil() as isize;
+                overlay.rect_filled(
+                    x0,
+                    (i * band_px) as isize,
+                    (x1 - x0 + 1) as usize,
+                    bar_height,
+                    color,
+                );
+            }
+        });
+
+        for (i, &(label, value, color)) in stages.iter().take(visible).enumerate() {
+            let row = (height - 1 - i * rows_per_stage - (rows_per_stage - 1) / 2) as isize;
+            let color = options.label_color.or(color);
+            self.text_at_cell(
+                &text::truncate(label, left.saturating_sub(1)),
+                0,
+                row,
+                color,
+            );
+
+            if options.show_percent {
+                let reference = match (i, options.relative_to_first) {
+                    (0, _) => value,
+                    (_, true) => stages[0].1,
+                    (_, false) => stages[i - 1].1,
+                };
+                if reference > 0.0 && value.is_finite() {
+                    let text = format!("{:.0}%", value / reference * 100.0);
+                    let col = width as isize - text::display_width(&text) as isize;
+                    self.text_at_cell(&text, col, row, color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FunnelOptions;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn stages_are_centered_and_show_conversion() {
+        let mut chart = ChartContext::new(14, 3);
+        chart.funnel_chart(
+            &[("a", 100.0, None), ("b", 50.0, None), ("c", 10.0, None)],
+            &FunnelOptions::default(),
+        );
+
+        assert_eq!(
+            chart.canvas.render_with_options(false, None),
+            "a⠀⠿⠿⠿⠿⠿⠿⠿⠀100%\nb⠀⠀⠸⠿⠿⠿⠇⠀⠀⠀50%\nc⠀⠀⠀⠀⠿⠀⠀⠀⠀⠀20%\n"
+        );
+    }
+}
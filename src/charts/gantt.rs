@@ -0,0 +1,192 @@
+use super::{ChartContext, Marker, MarkerShape};
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+
+/// Fila del Gantt: etiqueta, intervalos `(inicio, fin)` y color de las barras.
+pub type GanttRow<'a> = (&'a str, &'a [(f64, f64)], Option<Color>);
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GanttOptions {
+    /// Hitos como `(fila, instante)`, dibujados como rombos sobre la fila.
+    pub milestones: Vec<(usize, f64)>,
+    /// Instante actual, marcado con una línea vertical punteada.
+    pub now: Option<f64>,
+    pub now_color: Option<Color>,
+    pub axis_color: Option<Color>,
+    pub label_color: Option<Color>,
+}
+
+impl GanttOptions {
+    pub fn with_now(mut self, now: f64) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    pub fn with_milestone(mut self, row: usize, at: f64) -> Self {
+        self.milestones.push((row, at));
+        self
+    }
+}
+
+impl ChartContext {
+    /// Diagrama de Gantt: una fila de texto por entrada `(etiqueta, intervalos,
+    /// color)`, de arriba abajo, con las etiquetas en un margen izquierdo y el eje
+    /// de tiempo en las dos últimas filas. Las filas que no caben se omiten.
+    /// El rango de tiempo sale de los datos o de `set_view_range` (eje x).
+    pub fn gantt_chart(&mut self, rows: &[GanttRow], options: &GanttOptions) {
+        let width = self.canvas.width;
+        let height = self.canvas.height;
+        if rows.is_empty() || height < 3 || width < 4 {
+            return;
+        }
+
+        let Some(time_range) = self.gantt_time_range(rows, options) else {
+            return;
+        };
+        let longest = rows
+            .iter()
+            .map(|(label, ..)| text::display_width(label))
+            .max()
+            .unwrap_or(0);
+        let gutter = if longest == 0 {
+            0
+        } else {
+            longest.min(width / 3) + 1
+        };
+        let gutter_px = (gutter * 2) as f64;
+        let last_px = (self.canvas.pixel_width() - 1) as f64;
+        let span = (time_range.1 - time_range.0).max(1e-9);
+        let to_px = move |t: f64| {
+            (gutter_px + (t - time_range.0) / span * (last_px - gutter_px)).round() as isize
+        };
+
+        let visible_rows = rows.len().min(height - 2);
+        let axis_y = 4 * (height - 2) as isize;
+        let bottom_px = self.canvas.pixel_height() as isize - 1;
+
+        self.draw_background_overlay(|overlay| {
+            overlay.line_screen(
+                gutter_px as isize,
+                axis_y,
+                last_px as isize,
+                axis_y,
+                options.axis_color,
+            );
+            for tick in AxisScale::Linear.axis_ticks(time_range) {
+                let x = to_px(tick);
+                overlay.line_screen(x, axis_y, x, axis_y + 1, options.axis_color);
+            }
+        });
+
+        self.draw_foreground_overlay(|overlay| {
+            for (i, (_, intervals, color)) in rows.iter().take(visible_rows).enumerate() {
+                let top = 4 * i as isize;
+                for &(start, end) in intervals.iter() {
+                    if !start.is_finite() || !end.is_finite() {
+                        continue;
+                    }
+                    let (x0, x1) = (to_px(start.min(end)), to_px(start.max(end)));
+                    overlay.rect_filled(x0, top, (x1 - x0).max(1) as usize, 3, *color);
+                }
+            }
+
+            for &(row, at) in &options.milestones {
+                if row >= visible_rows || !at.is_finite() {
+                    continue;
+                }
+                // Centrado en la barra; sus puntas asoman a los huecos entre filas.
+                let (x, y) = (to_px(at), bottom_px - (4 * row as isize + 1));
+                Marker::new(MarkerShape::Diamond)
+                    .with_size(2)
+                    .filled(true)
+                    .draw(overlay, x, y, rows[row].2);
+            }
+
+            if let Some(now) = options.now.filter(|t| t.is_finite()) {
+                let x = to_px(now);
+                for y in (0..axis_y).step_by(2) {
+                    overlay.line_screen(x, y, x, y, options.now_color);
+                }
+            }
+        });
+
+        for (i, (label, ..)) in rows.iter().take(visible_rows).enumerate() {
+            let text = text::truncate(label, gutter.saturating_sub(1));
+            self.text_at_cell(&text, 0, (height - 1 - i) as isize, options.label_color);
+        }
+
+        // Etiquetas del eje centradas en su marca, sin solaparse entre sí.
+        let mut next_free = gutter as isize;
+        for tick in AxisScale::Linear.axis_ticks(time_range) {
+            let text = AxisScale::format_compact(tick);
+            let len = text::display_width(&text) as isize;
+            let tick_col = to_px(tick) / 2;
+            let col = (tick_col - len / 2)
+                .max(next_free)
+                .min(width as isize - len);
+            // Se desplaza si hace falta, pero siempre cubriendo su marca.
+            if col < next_free || col > tick_col {
+                continue;
+            }
+            self.text_at_cell(&text, col, 0, options.axis_color);
+            next_free = col + len + 1;
+        }
+    }
+
+    fn gantt_time_range(&self, rows: &[GanttRow], options: &GanttOptions) -> Option<(f64, f64)> {
+        if let Some((x_range, _)) = self.view_range {
+            return Some(x_range);
+        }
+        let times = rows
+            .iter()
+            .flat_map(|(_, intervals, _)| intervals.iter().flat_map(|&(s, e)| [s, e]))
+            .chain(options.milestones.iter().map(|&(_, t)| t))
+            .chain(options.now)
+            .filter(|t| t.is_finite());
+        let (min, max) = times.fold(None, |acc: Option<(f64, f64)>, t| {
+            Some(acc.map_or((t, t), |(lo, hi)| (lo.min(t), hi.max(t))))
+        })?;
+        Some(Self::expand_range(min, max, 0.02, AxisScale::Linear))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GanttOptions;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn bars_follow_the_time_axis_under_the_gutter() {
+        let mut chart = ChartContext::new(18, 4);
+        chart.set_view_range((0.0, 10.0), (0.0, 1.0));
+        let build: &[(f64, f64)] = &[(0.0, 5.0)];
+        let deploy: &[(f64, f64)] = &[(5.0, 10.0)];
+        chart.gantt_chart(
+            &[("ab", build, None), ("cd", deploy, None)],
+            &GanttOptions::default(),
+        );
+
+        assert_eq!(
+            chart.canvas.render_with_options(false, None),
+            "ab⠀⠿⠿⠿⠿⠿⠿⠿⠇⠀⠀⠀⠀⠀⠀⠀\ncd⠀⠀⠀⠀⠀⠀⠀⠀⠸⠿⠿⠿⠿⠿⠿⠇\n⠀⠀⠀⠋⠉⠉⠉⠉⠋⠉⠉⠉⠙⠉⠉⠉⠉⠙\n⠀⠀⠀0⠀⠀3.33⠀6.67⠀10\n"
+        );
+    }
+
+    #[test]
+    fn now_line_and_milestones_are_drawn_and_labels_truncated() {
+        let mut chart = ChartContext::new(12, 4);
+        chart.set_view_range((0.0, 10.0), (0.0, 1.0));
+        let empty: &[(f64, f64)] = &[];
+        let options = GanttOptions::default()
+            .with_now(10.0)
+            .with_milestone(0, 5.0);
+        chart.gantt_chart(&[("release-candidate", empty, None)], &options);
+
+        // Rombo del hito en t = 5 y línea punteada de `now` en el borde derecho.
+        assert_eq!(
+            chart.canvas.render_with_options(false, None),
+            "rel…⠀⠀⠀⠰⣷⠕⠀⠨\n⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠨\n⠀⠀⠀⠀⠀⠋⠉⠋⠉⠙⠉⠙\n⠀⠀⠀⠀⠀0⠀3.33⠀\n"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use super::ChartContext;
+use crate::text::{self, HAlign, VAlign};
+use colored::Color;
+
+impl ChartContext {
+    /// Escribe `text` anclado al punto normalizado `(x_norm, y_norm)`, como `text`,
+    /// pero con alineación: `HAlign::Center` centra cada línea en el ancla y
+    /// `VAlign::Top` cuelga el bloque debajo de ella. Los `\n` separan líneas.
+    pub fn text_aligned(
+        &mut self,
+        text: &str,
+        x_norm: f64,
+        y_norm: f64,
+        align: HAlign,
+        valign: VAlign,
+        color: Option<Color>,
+    ) {
+        let (col, row) = self.anchor_cell(x_norm, y_norm);
+        let lines: Vec<&str> = text.lines().collect();
+        let top = row + Self::rows_above(lines.len(), valign);
+        for (i, line) in lines.iter().enumerate() {
+            let width = text::display_width(line) as isize;
+            let start = match align {
+                HAlign::Left => col,
+                HAlign::Center => col - width / 2,
+                HAlign::Right => col - width + 1,
+            };
+            self.text_at_cell(line, start, top - i as isize, color);
+        }
+    }
+
+    /// Reparte `text` en líneas dentro de la caja `(col, row, width, height)` en
+    /// celdas, con `(col, row)` en su esquina inferior izquierda (fila 0 abajo).
+    ///
+    /// Devuelve cuántas líneas ocupa el texto completo; si son más que `height`,
+    /// la última visible termina en `…`.
+    pub fn text_box(
+        &mut self,
+        text: &str,
+        area: (usize, usize, usize, usize),
+        align: HAlign,
+        valign: VAlign,
+        color: Option<Color>,
+    ) -> usize {
+        let (col, row, width, height) = area;
+        let mut lines = text::wrap(text, width);
+        let needed = lines.len();
+        if needed > height {
+            lines.truncate(height);
+            if let Some(last) = lines.last_mut() {
+                // Si no queda una celda libre para `…`, se recorta la línea con él
+                // incluido para que siga ocupando todo el ancho.
+                if text::display_width(last) < width {
+                    last.push('…');
+                } else {
+                    *last = text::truncate(&format!("{last}…"), width);
+                }
+            }
+        }
+
+        let free = height - lines.len();
+        let skip = match valign {
+            VAlign::Top => 0,
+            VAlign::Middle => free / 2,
+            VAlign::Bottom => free,
+        };
+        let top = (row + height) as isize - 1 - skip as isize;
+        for (i, line) in lines.iter().enumerate() {
+            let slack = width.saturating_sub(text::display_width(line));
+            let offset = match align {
+                HAlign::Left => 0,
+                HAlign::Center => slack / 2,
+                HAlign::Right => slack,
+            };
+            self.text_at_cell(line, (col + offset) as isize, top - i as isize, color);
+        }
+        needed
+    }
+
+    /// Escribe `text` de arriba abajo, un grafema por fila, en la columna de
+    /// `x_norm`. Pensado para títulos del eje y y etiquetas de barras altas;
+    /// `valign` sitúa el bloque respecto a `y_norm` como en `text_aligned`.
+    pub fn text_vertical(
+        &mut self,
+        text: &str,
+        x_norm: f64,
+        y_norm: f64,
+        valign: VAlign,
+        color: Option<Color>,
+    ) {
+        let (col, row) = self.anchor_cell(x_norm, y_norm);
+        let glyphs: Vec<&str> = text::graphemes(text)
+            .filter(|g| text::grapheme_width(g) > 0)
+            .collect();
+        let top = row + Self::rows_above(glyphs.len(), valign);
+        for (i, g) in glyphs.iter().enumerate() {
+            self.text_at_cell(g, col, top - i as isize, color);
+        }
+    }
+
+    /// Celda del punto normalizado, con el mismo redondeo que `text`.
+    fn anchor_cell(&self, x_norm: f64, y_norm: f64) -> (isize, isize) {
+        let w = self.canvas.width.saturating_sub(1) as f64;
+        let h = self.canvas.height.saturating_sub(1) as f64;
+        ((x_norm * w).round() as isize, (y_norm * h).round() as isize)
+    }
+
+    /// Filas que un bloque de `lines` líneas sube por encima de su ancla.
+    fn rows_above(lines: usize, valign: VAlign) -> isize {
+        let lines = lines.max(1) as isize;
+        match valign {
+            VAlign::Top => 0,
+            VAlign::Middle => (lines - 1) / 2,
+            VAlign::Bottom => lines - 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::charts::ChartContext;
+    use crate::text::{HAlign, VAlign};
+
+    fn rows(chart: &ChartContext) -> Vec<String> {
+        chart
+            .canvas
+            .render_with_options(false, None)
+            .lines()
+            .map(|l| l.replace('⠀', "."))
+            .collect()
+    }
+
+    #[test]
+    fn aligned_text_is_placed_relative_to_its_anchor() {
+        let mut chart = ChartContext::new(9, 3);
+        chart.text_aligned("abc", 0.5, 1.0, HAlign::Center, VAlign::Top, None);
+        chart.text_aligned("xy\nz", 1.0, 0.0, HAlign::Right, VAlign::Bottom, None);
+        assert_eq!(rows(&chart), ["...abc...", ".......xy", "........z"]);
+    }
+
+    #[test]
+    fn text_box_wraps_aligns_and_marks_overflow() {
+        let mut chart = ChartContext::new(8, 4);
+        let needed = chart.text_box(
+            "uso de cpu alto",
+            (1, 0, 6, 4),
+            HAlign::Center,
+            VAlign::Middle,
+            None,
+        );
+        assert_eq!(needed, 3);
+        assert_eq!(
+            rows(&chart),
+            [".uso de.", "..cpu...", "..alto..", "........"]
+        );
+
+        let mut chart = ChartContext::new(8, 2);
+        chart.text_box(
+            "uso de cpu alto",
+            (0, 0, 8, 1),
+            HAlign::Left,
+            VAlign::Top,
+            None,
+        );
+        assert_eq!(rows(&chart), ["........", "uso de…."]);
+
+        // La última línea visible llena la caja: `…` sustituye a su último grafema.
+        let mut chart = ChartContext::new(6, 1);
+        chart.text_box("uso de cpu", (0, 0, 6, 1), HAlign::Left, VAlign::Top, None);
+        assert_eq!(rows(&chart), ["uso d…"]);
+    }
+
+    #[test]
+    fn vertical_text_stacks_one_grapheme_per_row() {
+        let mut chart = ChartContext::new(3, 5);
+        chart.text_vertical("CPU", 0.0, 0.5, VAlign::Middle, None);
+        chart.text_vertical("日本", 0.5, 0.0, VAlign::Bottom, None);
+        assert_eq!(rows(&chart), ["...", "C..", "P..", "U日", ".本"]);
+    }
+}
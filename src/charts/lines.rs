@@ -0,0 +1,209 @@
+use super::ChartContext;
+use crate::canvas::BrailleCanvas;
+use colored::Color;
+
+/// Cómo se unen dos muestras consecutivas de una serie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineInterpolation {
+    /// Segmento recto entre muestras.
+    #[default]
+    Linear,
+    /// El salto ocurre en la `x` de la muestra anterior: el valor nuevo rige desde ahí.
+    StepPre,
+    /// El valor se mantiene hasta la `x` de la siguiente muestra (contadores).
+    StepPost,
+    /// El salto ocurre a mitad de camino entre ambas muestras.
+    StepMid,
+    /// Curva cúbica monótona (Fritsch-Carlson): suave, pero nunca sobrepasa los
+    /// valores de las muestras vecinas.
+    Smooth,
+}
+
+impl ChartContext {
+    /// `line_chart` con un modo de interpolación explícito.
+    pub fn line_chart_interpolated(
+        &mut self,
+        points: &[(f64, f64)],
+        interpolation: LineInterpolation,
+        color: Option<Color>,
+    ) {
+        if points.len() < 2 {
+            return;
+        }
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.line_chart_with_ranges(points, x_range, y_range, interpolation, color);
+    }
+
+    /// Líneas verticales desde `baseline` hasta cada punto, rematadas con un
+    /// pequeño círculo. El rango automático incluye la línea base.
+    pub fn stem_plot(&mut self, points: &[(f64, f64)], baseline: f64, color: Option<Color>) {
+        if points.is_empty() {
+            return;
+        }
+        let mut extents = points.to_vec();
+        if let Some(&(x, _)) = points.iter().find(|(x, _)| x.is_finite()) {
+            extents.push((x, baseline));
+        }
+        let (x_range, y_range) = self.resolve_ranges(&extents);
+        let projection = self.projection(x_range, y_range);
+        // Una línea base no representable (p. ej. 0 en log) cae al borde inferior.
+        let base_py = |x: f64| {
+            projection
+                .map(x, baseline)
+                .map(|(_, py)| py)
+                .unwrap_or(projection.bottom_inset_px as isize)
+        };
+
+        self.draw_foreground_overlay(|overlay| {
+            for &(x, y) in points {
+                let Some((px, py)) = projection.map(x, y) else {
+                    continue;
+                };
+                overlay.line(px, base_py(x), px, py, color);
+                overlay.circle(px, py, 1, color);
+            }
+        });
+    }
+
+    /// Traza `run` (ya en píxeles) como una cúbica monótona. Donde la `x` no
+    /// avanza no hay función que interpolar y el tramo queda recto.
+    pub(super) fn draw_smooth_run(
+        overlay: &mut BrailleCanvas,
+        run: &[(isize, isize)],
+        color: Option<Color>,
+    ) {
+        let mut start = 0;
+        for i in 1..=run.len() {
+            if i < run.len() && run[i].0 > run[i - 1].0 {
+                continue;
+            }
+            let piece: Vec<(f64, f64)> = run[start..i]
+                .iter()
+                .map(|&(x, y)| (x as f64, y as f64))
+                .collect();
+            overlay.curve_impl(&monotone_cubic_segments(&piece), color, true);
+            if i < run.len() {
+                let (p0, p1) = (run[i - 1], run[i]);
+                overlay.line(p0.0, p0.1, p1.0, p1.1, color);
+            }
+            start = i;
+        }
+    }
+
+    /// Traza el tramo `p0 -> p1` (ya en píxeles) según `interpolation`. Todos los
+    /// tramos pasan por `BrailleCanvas::line`, así que heredan su recorte.
+    pub(super) fn draw_interpolated_segment(
+        overlay: &mut BrailleCanvas,
+        p0: (isize, isize),
+        p1: (isize, isize),
+        interpolation: LineInterpolation,
+        color: Option<Color>,
+    ) {
+        match interpolation {
+            LineInterpolation::Linear | LineInterpolation::Smooth => {
+                overlay.line(p0.0, p0.1, p1.0, p1.1, color)
+            }
+            LineInterpolation::StepPre => {
+                overlay.line(p0.0, p0.1, p0.0, p1.1, color);
+                overlay.line(p0.0, p1.1, p1.0, p1.1, color);
+            }
+            LineInterpolation::StepPost => {
+                overlay.line(p0.0, p0.1, p1.0, p0.1, color);
+                overlay.line(p1.0, p0.1, p1.0, p1.1, color);
+            }
+            LineInterpolation::StepMid => {
+                let mid = p0.0 + (p1.0 - p0.0) / 2;
+                overlay.line(p0.0, p0.1, mid, p0.1, color);
+                overlay.line(mid, p0.1, mid, p1.1, color);
+                overlay.line(mid, p1.1, p1.0, p1.1, color);
+            }
+        }
+    }
+}
+
+/// Tramos Bézier cúbicos encadenados de la interpolación de Fritsch-Carlson
+/// sobre `points`, con `x` estrictamente creciente.
+fn monotone_cubic_segments(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let n = points.len();
+    if n < 2 {
+        return points.to_vec();
+    }
+    let secants: Vec<f64> = points
+        .windows(2)
+        .map(|w| (w[1].1 - w[0].1) / (w[1].0 - w[0].0))
+        .collect();
+
+    let mut tangents = Vec::with_capacity(n);
+    tangents.push(secants[0]);
+    for k in 1..n - 1 {
+        let (d0, d1) = (secants[k - 1], secants[k]);
+        tangents.push(if d0 * d1 <= 0.0 { 0.0 } else { (d0 + d1) / 2.0 });
+    }
+    tangents.push(secants[n - 2]);
+
+    // Limita las tangentes para que cada tramo siga siendo monótono.
+    for (k, &d) in secants.iter().enumerate() {
+        if d == 0.0 {
+            tangents[k] = 0.0;
+            tangents[k + 1] = 0.0;
+            continue;
+        }
+        let (a, b) = (tangents[k] / d, tangents[k + 1] / d);
+        let norm = a * a + b * b;
+        if norm > 9.0 {
+            let t = 3.0 / norm.sqrt();
+            tangents[k] = t * a * d;
+            tangents[k + 1] = t * b * d;
+        }
+    }
+
+    let mut out = vec![points[0]];
+    for k in 0..n - 1 {
+        let ((x0, y0), (x1, y1)) = (points[k], points[k + 1]);
+        let third = (x1 - x0) / 3.0;
+        out.push((x0 + third, y0 + tangents[k] * third));
+        out.push((x1 - third, y1 - tangents[k + 1] * third));
+        out.push((x1, y1));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineInterpolation;
+    use crate::charts::ChartContext;
+
+    fn render(interpolation: LineInterpolation) -> String {
+        let mut chart = ChartContext::new(4, 1);
+        chart.set_view_range((0.0, 1.0), (0.0, 1.0));
+        chart.line_chart_interpolated(&[(0.0, 0.0), (1.0, 1.0)], interpolation, None);
+        chart.canvas.render_no_color()
+    }
+
+    #[test]
+    fn step_modes_place_the_jump_where_expected() {
+        assert_eq!(render(LineInterpolation::StepPre), "⡏⠉⠉⠉\n");
+        assert_eq!(render(LineInterpolation::StepPost), "⣀⣀⣀⣸\n");
+        assert_eq!(render(LineInterpolation::StepMid), "⣀⣸⠉⠉\n");
+    }
+
+    #[test]
+    fn smooth_interpolation_does_not_overshoot_flat_runs() {
+        let mut chart = ChartContext::new(8, 2);
+        chart.set_view_range((0.0, 3.0), (0.0, 1.0));
+        let points = [(0.0, 0.0), (1.0, 0.0), (2.0, 1.0), (3.0, 1.0)];
+        chart.line_chart_interpolated(&points, LineInterpolation::Smooth, None);
+
+        // Los tramos planos siguen planos: ni baja del suelo ni sube del techo.
+        assert_eq!(chart.canvas.render_no_color(), "⠀⠀⠀⠀⡸⠉⠉⠉\n⣀⣀⣀⡜⠀⠀⠀⠀\n");
+    }
+
+    #[test]
+    fn stems_start_at_the_baseline() {
+        let mut chart = ChartContext::new(3, 2);
+        chart.set_view_range((0.0, 2.0), (-1.0, 1.0));
+        chart.stem_plot(&[(1.0, 1.0)], 0.0, None);
+
+        assert_eq!(chart.canvas.render_no_color(), "⠀⢹⠁\n⠀⠀⠀\n");
+    }
+}
@@ -0,0 +1,184 @@
+use super::ChartContext;
+use crate::canvas::{BrailleCanvas, FillRule, LineStyle};
+use colored::Color;
+
+/// Forma de un marcador de dispersión.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerShape {
+    /// Disco relleno.
+    Dot,
+    Plus,
+    Cross,
+    Square,
+    Diamond,
+    Circle,
+    /// Triángulo con el vértice hacia arriba.
+    Triangle,
+    /// Carácter en la capa de texto (`●`, `×`, `▲`...), una celda por punto.
+    Glyph(char),
+}
+
+/// Marcador de dispersión: forma, radio en píxeles y relleno.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marker {
+    pub shape: MarkerShape,
+    /// Distancia del centro al borde, en píxeles; `0` es un único píxel.
+    pub size: usize,
+    /// Rellena `Square`, `Diamond`, `Circle` y `Triangle`.
+    pub filled: bool,
+}
+
+impl Default for Marker {
+    fn default() -> Self {
+        Self::new(MarkerShape::Dot)
+    }
+}
+
+impl Marker {
+    pub fn new(shape: MarkerShape) -> Self {
+        Self {
+            shape,
+            size: 2,
+            filled: false,
+        }
+    }
+
+    pub fn glyph(c: char) -> Self {
+        Self::new(MarkerShape::Glyph(c))
+    }
+
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Dibuja el marcador centrado en `(x, y)`, en píxeles cartesianos. Se
+    /// traza siempre continuo, sea cual sea el `line_style` del lienzo.
+    pub fn draw(&self, canvas: &mut BrailleCanvas, x: isize, y: isize, color: Option<Color>) {
+        let style = std::mem::replace(&mut canvas.line_style, LineStyle::SOLID);
+        let r = self.size as isize;
+
+        match self.shape {
+            _ if r == 0 && !matches!(self.shape, MarkerShape::Glyph(_)) => {
+                canvas.line(x, y, x, y, color);
+            }
+            MarkerShape::Dot => canvas.circle_filled(x, y, r, color),
+            MarkerShape::Plus => {
+                canvas.line(x - r, y, x + r, y, color);
+                canvas.line(x, y - r, x, y + r, color);
+            }
+            MarkerShape::Cross => {
+                canvas.line(x - r, y - r, x + r, y + r, color);
+                canvas.line(x - r, y + r, x + r, y - r, color);
+            }
+            MarkerShape::Circle if self.filled => canvas.circle_filled(x, y, r, color),
+            MarkerShape::Circle => canvas.circle(x, y, r, color),
+            MarkerShape::Square => {
+                let corners = [
+                    (x - r, y - r),
+                    (x + r, y - r),
+                    (x + r, y + r),
+                    (x - r, y + r),
+                ];
+                Self::outline(canvas, &corners, self.filled, color);
+            }
+            MarkerShape::Diamond => {
+                let corners = [(x, y - r), (x + r, y), (x, y + r), (x - r, y)];
+                Self::outline(canvas, &corners, self.filled, color);
+            }
+            MarkerShape::Triangle => {
+                let corners = [(x - r, y - r), (x + r, y - r), (x, y + r)];
+                Self::outline(canvas, &corners, self.filled, color);
+            }
+            MarkerShape::Glyph(c) => {
+                if x >= 0 && y >= 0 {
+                    canvas.set_char(x as usize / 2, y as usize / 4, c, color);
+                }
+            }
+        }
+
+        canvas.line_style = style;
+    }
+
+    fn outline(
+        canvas: &mut BrailleCanvas,
+        corners: &[(isize, isize)],
+        filled: bool,
+        color: Option<Color>,
+    ) {
+        if filled {
+            canvas.polygon_filled(corners, FillRule::NonZero, color);
+        }
+        for (i, &(x0, y0)) in corners.iter().enumerate() {
+            let (x1, y1) = corners[(i + 1) % corners.len()];
+            canvas.line(x0, y0, x1, y1, color);
+        }
+    }
+}
+
+impl ChartContext {
+    /// `scatter` con un marcador por punto en lugar de un único píxel.
+    pub fn scatter_with_marker(
+        &mut self,
+        points: &[(f64, f64)],
+        marker: Marker,
+        color: Option<Color>,
+    ) {
+        if points.is_empty() {
+            return;
+        }
+        let (x_range, y_range) = self.resolve_ranges(points);
+        let projection = self.projection(x_range, y_range);
+
+        self.draw_foreground_overlay(|overlay| {
+            for &(x, y) in points {
+                if let Some((px, py)) = projection.map(x, y) {
+                    marker.draw(overlay, px, py, color);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Marker, MarkerShape};
+    use crate::canvas::{BrailleCanvas, LineStyle};
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn shapes_are_drawn_around_the_center_pixel() {
+        let render = |marker: Marker| {
+            let mut canvas = BrailleCanvas::new(3, 2);
+            // El estilo discontinuo del lienzo no afecta a los marcadores.
+            canvas.line_style = LineStyle::dotted();
+            marker.draw(&mut canvas, 2, 3, None);
+            canvas.render_no_color()
+        };
+
+        assert_eq!(render(Marker::new(MarkerShape::Plus)), "⠀⡄⠀\n⠉⠏⠁\n");
+        assert_eq!(render(Marker::new(MarkerShape::Cross)), "⢄⢀⠄\n⠔⠑⠄\n");
+        assert_eq!(
+            render(Marker::new(MarkerShape::Square).filled(true)),
+            "⣤⣤⡄\n⠿⠿⠇\n"
+        );
+        assert_eq!(render(Marker::new(MarkerShape::Diamond)), "⢀⢄⠀\n⠑⠔⠁\n");
+    }
+
+    #[test]
+    fn glyph_markers_go_to_the_text_layer() {
+        let mut chart = ChartContext::new(4, 2);
+        chart.set_view_range((0.0, 1.0), (0.0, 1.0));
+        chart.scatter_with_marker(&[(0.0, 0.0), (1.0, 1.0)], Marker::glyph('●'), None);
+
+        assert_eq!(
+            chart.canvas.render_with_options(false, None),
+            "⠀⠀⠀●\n●⠀⠀⠀\n"
+        );
+    }
+}
@@ -0,0 +1,522 @@
+use super::{ChartContext, SeriesPatterns};
+use crate::canvas::FillPattern;
+use crate::text;
+use colored::Color;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+/// Texto que acompaña a cada porción, fuera del círculo y unido por una guía.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieLabels {
+    None,
+    Percent,
+    Label,
+    LabelAndPercent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PieChartOptions {
+    /// Radio interior como fracción del exterior (`0.0` = tarta, `>0.0` = donut).
+    pub inner_radius: f64,
+    /// Ancho/alto de un sub-píxel. Con fuentes donde la celda no mide 1:2 los
+    /// círculos salen achatados; este factor corrige el radio horizontal.
+    pub pixel_aspect: f64,
+    /// Ángulo inicial en radianes (0 = derecha, sentido antihorario).
+    pub start_angle: f64,
+    /// Hueco en píxeles entre porciones, útil para distinguirlas sin color.
+    pub slice_gap_px: f64,
+    pub labels: PieLabels,
+    pub leader_lines: bool,
+    pub label_color: Option<Color>,
+    /// Trama de cada porción (se repite si hay más porciones); vacío = la del
+    /// lienzo. Distingue las porciones sin depender del color.
+    pub patterns: SeriesPatterns,
+}
+
+impl Default for PieChartOptions {
+    fn default() -> Self {
+        Self {
+            inner_radius: 0.0,
+            pixel_aspect: 1.0,
+            start_angle: 0.0,
+            slice_gap_px: 1.0,
+            labels: PieLabels::None,
+            leader_lines: true,
+            label_color: None,
+            patterns: SeriesPatterns::default(),
+        }
+    }
+}
+
+impl PieChartOptions {
+    pub fn donut(inner_radius: f64) -> Self {
+        Self {
+            inner_radius,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_labels(mut self, labels: PieLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn with_patterns(mut self, patterns: &[FillPattern]) -> Self {
+        self.patterns = SeriesPatterns::new(patterns);
+        self
+    }
+}
+
+/// Posición de la etiqueta (celda de inicio) y de su guía (píxeles cartesianos).
+struct PieLabelPlacement {
+    col: isize,
+    row: isize,
+    leader_start: (isize, isize),
+    leader_elbow: (isize, isize),
+    leader_end: isize,
+}
+
+impl ChartContext {
+    /// Tarta (o donut) con porciones rellenas y etiquetas opcionales.
+    ///
+    /// Los valores no finitos o `<= 0` se ignoran. Cuando hay etiquetas el radio se
+    /// reduce para dejarles sitio a ambos lados, y las de un mismo lado se apilan en
+    /// filas distintas para no pisarse.
+    pub fn pie_chart_with(
+        &mut self,
+        slices: &[(&str, f64, Option<Color>)],
+        options: &PieChartOptions,
+    ) {
+        let total: f64 = slices
+            .iter()
+            .filter(|(_, v, _)| v.is_finite() && *v > 0.0)
+            .map(|(_, v, _)| *v)
+            .sum();
+        if total <= 1e-9 {
+            return;
+        }
+
+        let texts: Vec<String> = slices
+            .iter()
+            .map(|(label, value, _)| Self::pie_label_text(label, *value / total, options.labels))
+            .collect();
+        let label_cells = texts
+            .iter()
+            .map(|t| text::display_width(t))
+            .max()
+            .unwrap_or(0);
+
+        let w_px = self.canvas.pixel_width() as f64;
+        let h_px = self.canvas.pixel_height() as f64;
+        let aspect = if options.pixel_aspect.is_finite() && options.pixel_aspect > 0.0 {
+            options.pixel_aspect
+        } else {
+            1.0
+        };
+        let cx = ((w_px - 1.0) / 2.0).floor();
+        let cy = ((h_px - 1.0) / 2.0).floor();
+
+        // Margen reservado para guía + texto: horizontal en píxeles de la guía y
+        // las celdas del texto, vertical una fila por arriba y por abajo.
+        let (margin_x, margin_y) = if options.labels == PieLabels::None {
+            (0.0, 0.0)
+        } else {
+            (4.0 + label_cells as f64 * 2.0, 5.0)
+        };
+        let radius = ((cx - margin_x) * aspect).min(cy - margin_y).floor();
+        if radius < 1.0 {
+            return;
+        }
+        let inner = radius * options.inner_radius.clamp(0.0, 0.95);
+
+        // Ángulos acumulados de cada porción válida: (inicio, fin, índice).
+        let mut sectors = Vec::with_capacity(slices.len());
+        let mut angle = 0.0;
+        for (i, (_, value, _)) in slices.iter().enumerate() {
+            if !value.is_finite() || *value <= 0.0 {
+                continue;
+            }
+            let end = angle + value / total * TAU;
+            sectors.push((angle, end, i));
+            angle = end;
+        }
+
+        let start_angle = options.start_angle;
+        let placements = if options.labels == PieLabels::None {
+            Vec::new()
+        } else {
+            let labeled: Vec<(f64, usize, usize)> = sectors
+                .iter()
+                .filter(|(_, _, i)| !texts[*i].is_empty())
+                .map(|&(a0, a1, i)| {
+                    let mid = start_angle + (a0 + a1) / 2.0;
+                    (mid, text::display_width(&texts[i]), i)
+                })
+                .collect();
+            let angles: Vec<(f64, usize)> = labeled.iter().map(|&(a, len, _)| (a, len)).collect();
+            Self::layout_pie_labels((cx, cy), radius, aspect, &angles, self.canvas.height)
+                .into_iter()
+                .zip(labeled.iter().map(|&(_, _, i)| i))
+                .collect()
+        };
+
+        let gap = options.slice_gap_px.max(0.0) / 2.0;
+        let draw_gaps = gap > 0.0 && sectors.len() > 1;
+        let reach_x = (radius / aspect).ceil() as isize;
+        let reach_y = radius as isize;
+        let leader_lines = options.leader_lines;
+        let label_color = options.label_color;
+        let patterns = options.patterns;
+        let patterns = patterns.as_slice();
+
+        self.draw_foreground_overlay(|overlay| {
+            let canvas_pattern = overlay.fill_pattern;
+            for dy in -reach_y..=reach_y {
+                for dx in -reach_x..=reach_x {
+                    let x = dx as f64 * aspect;
+                    let y = dy as f64;
+                    let dist = (x * x + y * y).sqrt();
+                    if dist > radius + 0.5 || dist < inner - 0.5 {
+                        continue;
+                    }
+
+                    let theta = (y.atan2(x) - start_angle).rem_euclid(TAU);
+                    let Some(&(a0, a1, i)) = sectors
+                        .iter()
+                        .find(|(a0, a1, _)| theta >= *a0 && theta < *a1)
+                        .or(sectors.last())
+                    else {
+                        continue;
+                    };
+
+                    // Distancia (en píxeles) al borde radial más cercano.
+                    if draw_gaps && dist > 0.0 {
+                        let edge = (theta - a0).min(a1 - theta);
+                        if dist * edge.min(FRAC_PI_2).sin() < gap {
+                            continue;
+                        }
+                    }
+
+                    overlay.fill_pattern = if patterns.is_empty() {
+                        canvas_pattern
+                    } else {
+                        patterns[i % patterns.len()]
+                    };
+                    overlay.fill_pixel(cx as isize + dx, cy as isize + dy, slices[i].2, true);
+                }
+            }
+            overlay.fill_pattern = canvas_pattern;
+
+            if leader_lines {
+                for (label, i) in &placements {
+                    let color = label_color.or(slices[*i].2);
+                    let (x0, y0) = label.leader_start;
+                    let (x1, y1) = label.leader_elbow;
+                    overlay.line(x0, y0, x1, y1, color);
+                    overlay.line(x1, y1, label.leader_end, y1, color);
+                }
+            }
+        });
+
+        for (label, i) in &placements {
+            let color = options.label_color.or(slices[*i].2);
+            self.text_at_cell(&texts[*i], label.col, label.row, color);
+        }
+    }
+
+    /// Coloca las etiquetas `(ángulo, ancho)` de un círculo en un lienzo de `rows`
+    /// filas, en el mismo orden.
+    ///
+    /// Las de un mismo lado que caerían en la misma fila se separan en el orden
+    /// angular de sus guías, desde el horizonte del centro hacia fuera: las de la
+    /// mitad superior suben y las de la inferior bajan, en el sentido de su radio.
+    /// Solo en los bordes del lienzo se empujan de vuelta; si en el lado no caben
+    /// todas, las de arriba quedan fuera.
+    fn layout_pie_labels(
+        center: (f64, f64),
+        radius: f64,
+        aspect: f64,
+        labels: &[(f64, usize)],
+        rows: usize,
+    ) -> Vec<PieLabelPlacement> {
+        let place = |(angle, len): (f64, usize), row| {
+            Self::place_pie_label(center, radius, aspect, angle, len, row)
+        };
+        let mut assigned: Vec<isize> = labels.iter().map(|&l| place(l, None).row).collect();
+        for right in [true, false] {
+            // De abajo arriba en el orden de los arranques de las guías.
+            let mut side: Vec<usize> = (0..labels.len())
+                .filter(|&k| (labels[k].0.cos() >= 0.0) == right)
+                .collect();
+            side.sort_by(|&a, &b| labels[a].0.sin().total_cmp(&labels[b].0.sin()));
+            let split = side.partition_point(|&k| labels[k].0.sin() < 0.0);
+
+            let mut below: Option<isize> = None;
+            for &k in &side[split..] {
+                if let Some(row) = below {
+                    assigned[k] = assigned[k].max(row + 1);
+                }
+                below = Some(assigned[k]);
+            }
+            let mut above = side.get(split).map(|&k| assigned[k]);
+            for &k in side[..split].iter().rev() {
+                if let Some(row) = above {
+                    assigned[k] = assigned[k].min(row - 1);
+                }
+                above = Some(assigned[k]);
+            }
+
+            let mut ceiling = rows as isize;
+            for &k in side.iter().rev() {
+                assigned[k] = assigned[k].min(ceiling - 1);
+                ceiling = assigned[k];
+            }
+            let mut floor = 0;
+            for &k in &side {
+                assigned[k] = assigned[k].max(floor);
+                floor = assigned[k] + 1;
+            }
+        }
+        labels
+            .iter()
+            .zip(assigned)
+            .map(|(&label, row)| place(label, Some(row)))
+            .collect()
+    }
+
+    /// Coloca la etiqueta de una porción en la dirección `angle`, sin pisar el
+    /// círculo en su fila: a la derecha empieza tras el borde y a la izquierda
+    /// termina antes de él. Con `row` la etiqueta va en esa fila y el codo de la
+    /// guía se aleja por su mismo radio hasta alcanzarla, así que las guías de
+    /// porciones vecinas no se cruzan ni entran en el círculo.
+    fn place_pie_label(
+        center: (f64, f64),
+        radius: f64,
+        aspect: f64,
+        angle: f64,
+        len: usize,
+        row: Option<isize>,
+    ) -> PieLabelPlacement {
+        let (cx, cy) = center;
+        let (sin, cos) = angle.sin_cos();
+        let to_px = |r: f64| {
+            (
+                (cx + r * cos / aspect).round() as isize,
+                (cy + r * sin).round() as isize,
+            )
+        };
+        let leader_start = to_px(radius + 1.0);
+        let mut leader_elbow = to_px(radius + 3.0);
+
+        let natural_row = leader_elbow.1.div_euclid(4);
+        let row = row.unwrap_or(natural_row);
+        if row != natural_row {
+            // Hasta dos filas más lejos por el radio; si la fila está más cerca del
+            // centro que el codo natural, el codo se queda y solo baja o sube.
+            let target_y = row * 4 + 1;
+            let reach = if sin.abs() > 1e-9 {
+                (target_y as f64 - cy) / sin
+            } else {
+                f64::INFINITY
+            };
+            let reach = reach.clamp(radius + 3.0, radius + 11.0);
+            leader_elbow = (to_px(reach).0, target_y);
+        }
+        // Semiancho del círculo en la fila de texto (con un píxel de holgura).
+        let row_center = row as f64 * 4.0 + 1.5;
+        let dy = ((row_center - cy).abs() - 2.0).max(0.0);
+        let half_chord = (radius * radius - dy * dy).max(0.0).sqrt() / aspect + 1.0;
+
+        let len = len as isize;
+        if cos >= 0.0 {
+            let min_px = (cx + half_chord).ceil() as isize;
+            let start_px = (leader_elbow.0 + 2).max(min_px + 1);
+            let col = (start_px + 1).div_euclid(2);
+            PieLabelPlacement {
+                col,
+                row,
+                leader_start,
+                leader_elbow,
+                leader_end: col * 2 - 1,
+            }
+        } else {
+            let max_px = (cx - half_chord).floor() as isize;
+            let end_px = (leader_elbow.0 - 2).min(max_px - 1);
+            let end_col = (end_px - 1).div_euclid(2);
+            PieLabelPlacement {
+                col: end_col - len + 1,
+                row,
+                leader_start,
+                leader_elbow,
+                leader_end: (end_col + 1) * 2,
+            }
+        }
+    }
+
+    fn pie_label_text(label: &str, fraction: f64, labels: PieLabels) -> String {
+        if !fraction.is_finite() || fraction <= 0.0 {
+            return String::new();
+        }
+        let percent = format!("{:.0}%", fraction * 100.0);
+        match labels {
+            PieLabels::None => String::new(),
+            PieLabels::Percent => percent,
+            PieLabels::Label => label.to_string(),
+            PieLabels::LabelAndPercent if label.is_empty() => percent,
+            PieLabels::LabelAndPercent => format!("{label} {percent}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PieChartOptions, PieLabels};
+    use crate::canvas::FillPattern;
+    use crate::charts::ChartContext;
+    use std::f64::consts::TAU;
+
+    type Segment = ((isize, isize), (isize, isize));
+
+    fn rows(chart: &ChartContext) -> Vec<String> {
+        chart
+            .canvas
+            .render_with_options(false, None)
+            .lines()
+            .map(|l| l.replace('⠀', "."))
+            .collect()
+    }
+
+    fn lit_pixels(chart: &ChartContext) -> u32 {
+        chart
+            .canvas
+            .cell_masks()
+            .iter()
+            .map(|mask| mask.count_ones())
+            .sum()
+    }
+
+    #[test]
+    fn donut_leaves_the_center_empty() {
+        let mut pie = ChartContext::new(10, 5);
+        pie.pie_chart(&[(1.0, None)]);
+        let mut donut = ChartContext::new(10, 5);
+        donut.pie_chart_with(&[("", 1.0, None)], &PieChartOptions::donut(0.5));
+
+        assert!(lit_pixels(&pie) > lit_pixels(&donut));
+        let center = donut.canvas.cell_masks()[2 * 10 + 4];
+        assert_eq!(center, 0);
+    }
+
+    #[test]
+    fn slices_are_filled_with_their_patterns() {
+        // Sin color, la trama es lo único que separa las dos mitades.
+        let mut chart = ChartContext::new(10, 5);
+        let options = PieChartOptions::default()
+            .with_patterns(&[FillPattern::Solid, FillPattern::Checkerboard]);
+        chart.pie_chart_with(&[("a", 1.0, None), ("b", 1.0, None)], &options);
+        assert_eq!(
+            chart.canvas.render_no_color(),
+            "⠀⢀⣤⣶⣶⣶⣦⣄⠀⠀\n⣰⣿⣿⣿⣿⣿⣿⣿⣷⡀\n⢛⢛⢛⢛⢻⢛⢛⢛⢛⠃\n⢑⢕⢕⢕⢕⢕⢕⢕⢕⠁\n⠀⠑⠕⢕⢕⢕⠕⠕⠁⠀\n"
+        );
+
+        // Sin tramas por porción se usa la del lienzo.
+        let mut chart = ChartContext::new(10, 5);
+        chart.canvas.fill_pattern = FillPattern::HorizontalLines;
+        chart.pie_chart(&[(1.0, None)]);
+        assert_eq!(
+            chart.canvas.render_no_color(),
+            "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠉⠉⠉⠉⠉⠉⠉⠁⠀\n⠉⠉⠉⠉⠉⠉⠉⠉⠉⠁\n⠉⠉⠉⠉⠉⠉⠉⠉⠉⠁\n⠀⠉⠉⠉⠉⠉⠉⠉⠁⠀\n"
+        );
+    }
+
+    #[test]
+    fn percent_labels_are_written_outside_the_circle() {
+        let mut chart = ChartContext::new(30, 8);
+        let options = PieChartOptions::default().with_labels(PieLabels::Percent);
+        chart.pie_chart_with(&[("a", 3.0, None), ("b", 1.0, None)], &options);
+
+        assert_eq!(
+            rows(&chart),
+            [
+                "..............................",
+                "......75%⣀⡀.⣀⣤⣤⣤⣄⡀............",
+                "..........⣨⣾⣿⣿⣿⣿⣿⣿⣦⡀..........",
+                ".........⢰⣿⣿⣿⣿⣿⣿⣿⣿⣿⣷..........",
+                ".........⢸⣿⣿⣿⣿⡏⣶⣶⣶⣶⣶..........",
+                "..........⠻⣿⣿⣿⡇⣿⣿⣿⡿⠃..........",
+                "...........⠈⠛⠿⠇⠿⠟⠋⠈⠒25%.......",
+                "..............................",
+            ]
+        );
+    }
+
+    /// Si los segmentos cerrados `a` y `b` se tocan o se cruzan.
+    fn segments_intersect(a: Segment, b: Segment) -> bool {
+        let orient = |p: (isize, isize), q: (isize, isize), r: (isize, isize)| {
+            ((q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0)).signum()
+        };
+        let within = |p: (isize, isize), q: (isize, isize), r: (isize, isize)| {
+            r.0 >= p.0.min(q.0) && r.0 <= p.0.max(q.0) && r.1 >= p.1.min(q.1) && r.1 <= p.1.max(q.1)
+        };
+        let (o1, o2) = (orient(a.0, a.1, b.0), orient(a.0, a.1, b.1));
+        let (o3, o4) = (orient(b.0, b.1, a.0), orient(b.0, b.1, a.1));
+        (o1 != o2 && o3 != o4)
+            || (o1 == 0 && within(a.0, a.1, b.0))
+            || (o2 == 0 && within(a.0, a.1, b.1))
+            || (o3 == 0 && within(b.0, b.1, a.0))
+            || (o4 == 0 && within(b.0, b.1, a.1))
+    }
+
+    #[test]
+    fn crowded_labels_get_separate_rows_and_leaders_that_do_not_cross() {
+        // Geometría de una tarta de 24x8 celdas con etiquetas de hasta 3 celdas:
+        // centro (23, 15) y radio 10. Las tres porciones pequeñas caerían en dos
+        // filas a la derecha.
+        let (center, radius) = ((23.0, 15.0), 10.0);
+        let labels: Vec<(f64, usize)> = [0.5, 1.5, 2.5, 13.0]
+            .iter()
+            .map(|mid| (mid / 23.0 * TAU, 1))
+            .collect();
+        let placements = ChartContext::layout_pie_labels(center, radius, 1.0, &labels, 8);
+
+        let rows: Vec<isize> = placements.iter().map(|p| p.row).collect();
+        assert_eq!(rows, [4, 5, 6, 2]);
+
+        let leaders: Vec<[Segment; 2]> = placements
+            .iter()
+            .map(|p| {
+                let turn = (p.leader_end, p.leader_elbow.1);
+                [(p.leader_start, p.leader_elbow), (p.leader_elbow, turn)]
+            })
+            .collect();
+        for (i, a) in leaders.iter().enumerate() {
+            for b in &leaders[i + 1..] {
+                for (sa, sb) in a.iter().flat_map(|sa| b.iter().map(move |sb| (*sa, *sb))) {
+                    assert!(!segments_intersect(sa, sb), "{sa:?} cruza {sb:?}");
+                }
+            }
+            // Ninguna guía entra en el círculo.
+            for point in [a[0].0, a[0].1] {
+                let (dx, dy) = (point.0 as f64 - center.0, point.1 as f64 - center.1);
+                assert!((dx * dx + dy * dy).sqrt() > radius, "{point:?}");
+            }
+        }
+
+        let mut chart = ChartContext::new(24, 8);
+        let options = PieChartOptions::default().with_labels(PieLabels::Label);
+        chart.pie_chart_with(
+            &[
+                ("x", 1.0, None),
+                ("y", 1.0, None),
+                ("z", 1.0, None),
+                ("big", 20.0, None),
+            ],
+            &options,
+        );
+        let rendered = chart.canvas.render_with_options(false, None);
+        let line_of = |label: char| rendered.lines().position(|l| l.contains(label));
+        assert_eq!(
+            [line_of('z'), line_of('y'), line_of('x')],
+            [Some(1), Some(2), Some(3)]
+        );
+    }
+}
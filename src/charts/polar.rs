@@ -0,0 +1,462 @@
+use super::{ChartContext, Projection};
+use crate::canvas::{BrailleCanvas, FillRule};
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+/// Sistema polar ya resuelto: `(theta, r)` pasa a `(r·cos θ, r·sin θ)` y de ahí
+/// a píxeles con la misma `Projection` que los gráficos cartesianos.
+#[derive(Debug, Clone, Copy)]
+struct PolarFrame {
+    projection: Projection,
+    /// Factor aplicado a `r` antes de proyectar (el radar encoge el marco para
+    /// dejar sitio a sus etiquetas).
+    shrink: f64,
+}
+
+impl PolarFrame {
+    fn to_px_f64(self, theta: f64, r: f64) -> Option<(f64, f64)> {
+        if !theta.is_finite() || !r.is_finite() || r < 0.0 {
+            return None;
+        }
+        let r = r * self.shrink;
+        self.projection.map_f64(r * theta.cos(), r * theta.sin())
+    }
+
+    fn to_px(self, theta: f64, r: f64) -> Option<(isize, isize)> {
+        let (x, y) = self.to_px_f64(theta, r)?;
+        Some((x.round() as isize, y.round() as isize))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RadarOptions {
+    /// Valor que corresponde al borde exterior; por defecto el máximo de los datos.
+    pub max_value: Option<f64>,
+    /// Número de anillos poligonales de la telaraña (0 = sin rejilla).
+    pub rings: usize,
+    pub filled: bool,
+    pub grid_color: Option<Color>,
+    pub label_color: Option<Color>,
+}
+
+impl Default for RadarOptions {
+    fn default() -> Self {
+        Self {
+            max_value: None,
+            rings: 4,
+            filled: false,
+            grid_color: None,
+            label_color: None,
+        }
+    }
+}
+
+impl ChartContext {
+    /// Fija el radio exterior del modo polar; sin él cada gráfico usa el máximo
+    /// de sus datos y `polar_grid` el de la última serie polar dibujada (1.0 si
+    /// aún no hay ninguna).
+    ///
+    /// Con `set_view_range` la ventana se da en las coordenadas cartesianas
+    /// `(r·cos θ, r·sin θ)`, para hacer zoom o desplazarse; este radio sigue
+    /// marcando el anillo exterior de `polar_grid`.
+    pub fn set_polar_range(&mut self, r_max: f64) -> &mut Self {
+        self.polar_r_max = (r_max.is_finite() && r_max > 0.0).then_some(r_max);
+        self
+    }
+
+    /// Proyección polar: la ventana de `set_view_range` o, sin ella, un rango
+    /// simétrico alrededor de `r_max` ajustado al aspecto del área de dibujo,
+    /// para que los círculos salgan redondos. Las escalas se fuerzan a lineales:
+    /// un eje logarítmico no admite las `x`/`y` negativas de medio círculo.
+    fn polar_frame(&self, r_max: f64) -> PolarFrame {
+        let (x_range, y_range) = self.view_range.unwrap_or_else(|| {
+            let (left, bottom) = self.canvas.plot_insets();
+            let w = self.canvas.pixel_width().saturating_sub(1 + left).max(1) as f64;
+            let h = self.canvas.pixel_height().saturating_sub(1 + bottom).max(1) as f64;
+            let (x_half, y_half) = if w >= h {
+                (r_max * w / h, r_max)
+            } else {
+                (r_max, r_max * h / w)
+            };
+            ((-x_half, x_half), (-y_half, y_half))
+        });
+        let mut projection = self.projection(x_range, y_range);
+        projection.x_scale = AxisScale::Linear;
+        projection.y_scale = AxisScale::Linear;
+        PolarFrame {
+            projection,
+            shrink: 1.0,
+        }
+    }
+
+    /// Radio exterior de una serie: el fijado con `set_polar_range` o el máximo
+    /// de sus datos con un 5% de margen, que se guarda para que `polar_grid`
+    /// rotule los anillos con la misma escala.
+    fn polar_auto_range(&mut self, points: &[(f64, f64)]) -> f64 {
+        if let Some(r_max) = self.polar_r_max {
+            return r_max;
+        }
+        let max = points
+            .iter()
+            .filter(|(t, r)| t.is_finite() && r.is_finite())
+            .map(|(_, r)| *r)
+            .fold(0.0, f64::max);
+        let r_max = if max > 1e-9 { max * 1.05 } else { 1.0 };
+        self.polar_auto_r_max = Some(r_max);
+        r_max
+    }
+
+    /// Serie en coordenadas polares `(theta en radianes, r)`, unida con líneas.
+    pub fn polar_line(&mut self, points: &[(f64, f64)], color: Option<Color>) {
+        if points.len() < 2 {
+            return;
+        }
+        let r_max = self.polar_auto_range(points);
+        let frame = self.polar_frame(r_max);
+        self.draw_foreground_overlay(|overlay| {
+            overlay.with_path(|overlay| {
+                for window in points.windows(2) {
+                    let (Some(p0), Some(p1)) = (
+                        frame.to_px(window[0].0, window[0].1),
+                        frame.to_px(window[1].0, window[1].1),
+                    ) else {
+                        continue;
+                    };
+                    overlay.line(p0.0, p0.1, p1.0, p1.1, color);
+                }
+            });
+        });
+    }
+
+    pub fn polar_scatter(&mut self, points: &[(f64, f64)], color: Option<Color>) {
+        if points.is_empty() {
+            return;
+        }
+        let r_max = self.polar_auto_range(points);
+        let frame = self.polar_frame(r_max);
+        self.draw_foreground_overlay(|overlay| {
+            for &(theta, r) in points {
+                if let Some((px, py)) = frame.to_px(theta, r) {
+                    if px >= 0 && py >= 0 {
+                        overlay.set_pixel(px as usize, py as usize, color);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Rejilla polar de fondo: `rings` circunferencias equiespaciadas con su valor
+    /// de radio y `spokes` radios desde el centro (el primero en theta = 0).
+    ///
+    /// Sin `set_polar_range` usa el rango automático de la última serie polar, así
+    /// que conviene llamarla después de dibujar los datos (la rejilla va al fondo).
+    pub fn polar_grid(&mut self, rings: usize, spokes: usize, color: Option<Color>) {
+        let r_max = self.polar_r_max.or(self.polar_auto_r_max).unwrap_or(1.0);
+        let frame = self.polar_frame(r_max);
+        let Some((cx, cy)) = frame.to_px(0.0, 0.0) else {
+            return;
+        };
+
+        self.draw_background_overlay(|overlay| {
+            for i in 1..=rings {
+                let r = r_max * i as f64 / rings as f64;
+                let (Some((ex, _)), Some((_, ey))) =
+                    (frame.to_px(0.0, r), frame.to_px(FRAC_PI_2, r))
+                else {
+                    continue;
+                };
+                // Con una ventana no proporcionada los anillos salen elípticos.
+                let (rx, ry) = (ex - cx, ey - cy);
+                if rx == ry {
+                    overlay.circle(cx, cy, rx, color);
+                } else {
+                    overlay.ellipse(cx, cy, rx, ry, color);
+                }
+            }
+            for i in 0..spokes {
+                let theta = TAU * i as f64 / spokes as f64;
+                if let Some((x, y)) = frame.to_px(theta, r_max) {
+                    overlay.line(cx, cy, x, y, color);
+                }
+            }
+        });
+
+        // Valores de los anillos sobre el radio vertical superior.
+        for i in 1..=rings {
+            let r = r_max * i as f64 / rings as f64;
+            let Some((px, py)) = frame.to_px(FRAC_PI_2, r) else {
+                continue;
+            };
+            let label = AxisScale::Linear.format_tick(r);
+            self.text_at_cell(&label, px / 2 + 1, py / 4, color);
+        }
+    }
+
+    /// Gráfico de radar: cada perfil es `(valores por métrica, color)` y `labels`
+    /// nombra las métricas, la primera arriba y el resto en sentido horario.
+    pub fn radar_chart(
+        &mut self,
+        labels: &[&str],
+        profiles: &[(&[f64], Option<Color>)],
+        options: &RadarOptions,
+    ) {
+        let metrics = labels.len();
+        if metrics < 3 {
+            return;
+        }
+        let max_value = options
+            .max_value
+            .filter(|v| v.is_finite() && *v > 0.0)
+            .unwrap_or_else(|| {
+                profiles
+                    .iter()
+                    .flat_map(|(values, _)| values.iter())
+                    .filter(|v| v.is_finite())
+                    .fold(0.0, |max: f64, v| max.max(*v))
+            });
+        if max_value <= 1e-9 {
+            return;
+        }
+
+        // Marco reducido para dejar sitio a las etiquetas a ambos lados.
+        let label_cells = labels
+            .iter()
+            .map(|l| text::display_width(l))
+            .max()
+            .unwrap_or(0);
+        let full = self.polar_frame(max_value);
+        let (Some((cx, cy)), Some((edge_x, _))) =
+            (full.to_px_f64(0.0, 0.0), full.to_px_f64(0.0, max_value))
+        else {
+            return;
+        };
+        let w_px = self.canvas.pixel_width() as f64;
+        let h_px = self.canvas.pixel_height() as f64;
+        let radius_px = (cx.min(w_px - 1.0 - cx) - 2.0 - label_cells as f64 * 2.0)
+            .min(cy.min(h_px - 1.0 - cy) - 5.0)
+            .floor();
+        if radius_px < 2.0 || edge_x - cx <= 0.0 {
+            return;
+        }
+        let frame = PolarFrame {
+            shrink: radius_px / (edge_x - cx),
+            ..full
+        };
+        let angle = |i: usize| FRAC_PI_2 - TAU * i as f64 / metrics as f64;
+        let ring_vertices = |scale: f64| -> Vec<(isize, isize)> {
+            (0..metrics)
+                .filter_map(|i| frame.to_px(angle(i), max_value * scale))
+                .collect()
+        };
+
+        let grid_color = options.grid_color;
+        let rings = options.rings;
+        self.draw_background_overlay(|overlay| {
+            for ring in 1..=rings {
+                let vertices = ring_vertices(ring as f64 / rings as f64);
+                Self::stroke_closed(overlay, &vertices, grid_color);
+            }
+            let center = (cx.round() as isize, cy.round() as isize);
+            for (x, y) in ring_vertices(1.0) {
+                overlay.line(center.0, center.1, x, y, grid_color);
+            }
+        });
+
+        for &(values, color) in profiles {
+            let vertices: Vec<(isize, isize)> = (0..metrics)
+                .filter_map(|i| {
+                    let v = values.get(i).copied().filter(|v| v.is_finite())?;
+                    frame.to_px(angle(i), v.clamp(0.0, max_value))
+                })
+                .collect();
+            if vertices.len() < 2 {
+                continue;
+            }
+            let filled = options.filled;
+            self.draw_foreground_overlay(|overlay| {
+                if filled {
+                    overlay.polygon_filled(&vertices, FillRule::EvenOdd, color);
+                }
+                Self::stroke_closed(overlay, &vertices, color);
+            });
+        }
+
+        for (i, label) in labels.iter().enumerate() {
+            let Some((px, py)) = frame.to_px(angle(i), max_value * (1.0 + 3.0 / radius_px)) else {
+                continue;
+            };
+            let len = text::display_width(label) as isize;
+            let cos = angle(i).cos();
+            let col = if cos.abs() < 0.2 {
+                px / 2 - len / 2
+            } else if cos > 0.0 {
+                px / 2 + 1
+            } else {
+                px / 2 - len
+            };
+            let color = options.label_color.or(grid_color);
+            self.text_at_cell(label, col, py.div_euclid(4), color);
+        }
+    }
+
+    fn stroke_closed(
+        overlay: &mut BrailleCanvas,
+        vertices: &[(isize, isize)],
+        color: Option<Color>,
+    ) {
+        overlay.with_path(|overlay| {
+            for (i, &(x0, y0)) in vertices.iter().enumerate() {
+                let (x1, y1) = vertices[(i + 1) % vertices.len()];
+                overlay.line(x0, y0, x1, y1, color);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RadarOptions;
+    use crate::charts::ChartContext;
+    use crate::scale::AxisScale;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn polar_points_are_centered_and_isotropic() {
+        let mut chart = ChartContext::new(9, 4);
+        chart.set_polar_range(1.0);
+        chart.polar_scatter(
+            &[(0.0, 1.0), (PI, 1.0), (PI / 2.0, 1.0), (-PI / 2.0, 1.0)],
+            None,
+        );
+
+        let masks = chart.canvas.cell_masks();
+        let lit: u32 = masks.iter().map(|m| m.count_ones()).sum();
+        assert_eq!(lit, 4);
+        // Derecha/izquierda a la misma distancia del centro que arriba/abajo.
+        assert_eq!(
+            chart.canvas.render_no_color(),
+            "⠀⠀⠀⠀⠈⠀⠀⠀⠀\n⢀⠀⠀⠀⠀⠀⠀⠀⡀\n⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⢀⠀⠀⠀⠀\n"
+        );
+    }
+
+    #[test]
+    fn polar_view_range_zooms_into_the_cartesian_window() {
+        // Ventana sobre el primer cuadrante: 20x20 px, 19 px por unidad; theta = PI
+        // cae fuera.
+        let mut chart = ChartContext::new(10, 5);
+        chart.set_view_range((0.0, 1.0), (0.0, 1.0));
+        chart.polar_scatter(
+            &[
+                (0.0, 1.0),
+                (PI / 2.0, 1.0),
+                (PI / 4.0, 0.5f64.sqrt()),
+                (PI, 1.0),
+            ],
+            None,
+        );
+
+        let lit: u32 = chart
+            .canvas
+            .cell_masks()
+            .iter()
+            .map(|m| m.count_ones())
+            .sum();
+        assert_eq!(lit, 3);
+        assert_eq!(
+            chart.canvas.render_no_color(),
+            "⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀⠂⠀⠀⠀⠀\n⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀\n"
+        );
+    }
+
+    #[test]
+    fn polar_charts_ignore_axis_scales() {
+        let points = [(0.0, 1.0), (PI / 3.0, 0.5), (PI, 0.8)];
+        let mut plain = ChartContext::new(12, 5);
+        plain.polar_line(&points, None);
+
+        let mut scaled = ChartContext::new(12, 5);
+        scaled.set_scales(AxisScale::Log10, AxisScale::Log10);
+        scaled.polar_line(&points, None);
+
+        assert_eq!(
+            plain.canvas.render_no_color(),
+            "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀⣀⡠⠤⣀⠀⠀⠀\n⠀⠀⠒⠉⠉⠀⠀⠀⠀⠉⠒⠀\n⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n"
+        );
+        assert_eq!(
+            scaled.canvas.render_no_color(),
+            plain.canvas.render_no_color()
+        );
+    }
+
+    #[test]
+    fn polar_grid_uses_the_auto_range_of_the_last_series() {
+        let mut chart = ChartContext::new(30, 10);
+        chart.polar_line(&[(0.0, 50.0), (1.0, 20.0), (2.0, 40.0), (3.0, 10.0)], None);
+        chart.polar_grid(2, 4, None);
+
+        // El anillo exterior es r = 50 * 1.05 y pasa justo por fuera del punto (0, 50).
+        let rows: Vec<String> = chart
+            .canvas
+            .render_with_options(false, None)
+            .lines()
+            .map(|l| l.replace('⠀', "."))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "..........⢀⠤⠒⠊⠉⡏52.5..........",
+                "........⡠⠊⠁⢠⣀..⡇....⠉⠢⡀.......",
+                "......⢀⠜...⠈⡆⣑⠦⣧26.2..⠘⢄......",
+                "......⡎....⡠⢻..⡇⠉⠚⠦⣀...⠈⡆.....",
+                ".....⣸⣀⣀⣀⣀⣰⣁⣈⣆⣀⣇⣀⣀⣀⣱⣉⣑⣢⣄⣸.....",
+                ".....⠸⡀...⠘⡄...⡇...⡜....⡸.....",
+                "......⢣....⠈⠢⣀.⡇⢀⡠⠊....⢠⠃.....",
+                ".......⠱⡀.....⢹⠉⠁.....⡰⠁......",
+                "........⠈⠢⢄...⢸....⢀⠤⠊........",
+                "...........⠉⠒⠢⢼⠤⠤⠒⠊⠁..........",
+            ]
+        );
+    }
+
+    #[test]
+    fn radar_writes_metric_labels_and_fills_profiles() {
+        let mut outlined = ChartContext::new(30, 10);
+        let labels = ["cpu", "mem", "disk", "net"];
+        let values = [1.0, 0.5, 1.0, 0.5];
+        outlined.radar_chart(
+            &labels,
+            &[(&values, None)],
+            &RadarOptions {
+                rings: 0,
+                ..RadarOptions::default()
+            },
+        );
+
+        let mut filled = ChartContext::new(30, 10);
+        filled.radar_chart(
+            &labels,
+            &[(&values, None)],
+            &RadarOptions {
+                rings: 0,
+                filled: true,
+                ..RadarOptions::default()
+            },
+        );
+
+        let rendered = outlined.canvas.render_with_options(false, None);
+        for label in labels {
+            assert!(rendered.contains(label), "missing {label}");
+        }
+        let lit = |chart: &ChartContext| -> u32 {
+            chart
+                .canvas
+                .cell_masks()
+                .iter()
+                .map(|m| m.count_ones())
+                .sum()
+        };
+        assert!(lit(&filled) > lit(&outlined));
+    }
+}
@@ -0,0 +1,198 @@
+use super::{ChartContext, LineInterpolation};
+use crate::scale::AxisScale;
+use crate::stats::{self, LinearFit, PolynomialFit};
+use colored::Color;
+
+/// Media móvil para suavizar una serie.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovingAverage {
+    /// Media de los últimos `n` puntos (la curva empieza en el punto `n`).
+    Simple(usize),
+    /// Media exponencial con factor `alpha` en (0, 1]; mayor = menos suave.
+    Exponential(f64),
+}
+
+impl ChartContext {
+    /// Recta de mínimos cuadrados sobre `points`, con la ecuación y el R² escritos
+    /// en la esquina superior izquierda. Usa el mismo rango que `scatter` o
+    /// `line_chart` sobre esos puntos, así que se superpone a la serie.
+    pub fn linear_fit(&mut self, points: &[(f64, f64)], color: Option<Color>) -> Option<LinearFit> {
+        let fit = LinearFit::from_points(points)?;
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.draw_fitted_curve(x_range, x_range, y_range, |x| fit.eval(x), color);
+
+        let intercept = if fit.intercept < 0.0 {
+            format!(" - {}", AxisScale::format_compact(-fit.intercept))
+        } else {
+            format!(" + {}", AxisScale::format_compact(fit.intercept))
+        };
+        let label = format!(
+            "y = {}x{}  R² = {:.3}",
+            AxisScale::format_compact(fit.slope),
+            intercept,
+            fit.r_squared
+        );
+        let (left_px, _) = self.canvas.plot_insets();
+        let col = left_px.div_ceil(2) as isize;
+        let row = self.canvas.height as isize - 1;
+        self.text_at_cell(&label, col, row, color);
+        Some(fit)
+    }
+
+    /// Polinomio de mínimos cuadrados de grado `degree` sobre `points`.
+    pub fn polynomial_fit(
+        &mut self,
+        points: &[(f64, f64)],
+        degree: usize,
+        color: Option<Color>,
+    ) -> Option<PolynomialFit> {
+        let fit = PolynomialFit::from_points(points, degree)?;
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.draw_fitted_curve(x_range, x_range, y_range, |x| fit.eval(x), color);
+        Some(fit)
+    }
+
+    /// Suavizado LOESS (regresión lineal local, pesos tricúbicos). `span` es la
+    /// fracción de puntos que entra en cada ajuste local; 0.3..0.7 es lo habitual.
+    pub fn loess(&mut self, points: &[(f64, f64)], span: f64, color: Option<Color>) {
+        let mut sorted: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        if sorted.len() < 2 {
+            return;
+        }
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let data_x = (sorted[0].0, sorted[sorted.len() - 1].0);
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.draw_fitted_curve(
+            data_x,
+            x_range,
+            y_range,
+            |x| stats::loess_at(&sorted, span, x).unwrap_or(f64::NAN),
+            color,
+        );
+    }
+
+    /// Media móvil simple o exponencial de la serie, ordenada por x.
+    pub fn moving_average(
+        &mut self,
+        points: &[(f64, f64)],
+        kind: MovingAverage,
+        color: Option<Color>,
+    ) {
+        let mut sorted: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let smoothed: Vec<(f64, f64)> = match kind {
+            MovingAverage::Simple(window) => {
+                let window = window.max(1);
+                let mut sum = 0.0;
+                sorted
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, &(x, y))| {
+                        sum += y;
+                        if i >= window {
+                            sum -= sorted[i - window].1;
+                        }
+                        (i + 1 >= window).then(|| (x, sum / window as f64))
+                    })
+                    .collect()
+            }
+            MovingAverage::Exponential(alpha) => {
+                let alpha = if alpha > 0.0 { alpha.min(1.0) } else { 1.0 };
+                let mut current = None;
+                sorted
+                    .iter()
+                    .map(|&(x, y)| {
+                        let value = current.map_or(y, |prev: f64| prev + alpha * (y - prev));
+                        current = Some(value);
+                        (x, value)
+                    })
+                    .collect()
+            }
+        };
+        if smoothed.len() < 2 {
+            return;
+        }
+        let (x_range, y_range) = self.resolve_ranges(points);
+        self.line_chart_with_ranges(
+            &smoothed,
+            x_range,
+            y_range,
+            LineInterpolation::Linear,
+            color,
+        );
+    }
+
+    /// Dibuja `model` en `domain`, muestreado a resolución de píxel en el espacio
+    /// (posiblemente logarítmico) del eje x.
+    fn draw_fitted_curve<F: Fn(f64) -> f64>(
+        &mut self,
+        domain: (f64, f64),
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        model: F,
+        color: Option<Color>,
+    ) {
+        let Some((t0, t1)) = self.x_scale.transformed_range(domain) else {
+            return;
+        };
+        let steps = self.canvas.pixel_width().max(2);
+        let points: Vec<(f64, f64)> = (0..=steps)
+            .map(|i| {
+                let x = self
+                    .x_scale
+                    .inverse_transform(t0 + (t1 - t0) * i as f64 / steps as f64);
+                (x, model(x))
+            })
+            .collect();
+        self.line_chart_with_ranges(&points, x_range, y_range, LineInterpolation::Linear, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MovingAverage;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn linear_fit_overlays_the_series_and_prints_the_equation() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)];
+        let mut fitted = ChartContext::new(24, 6);
+        let fit = fitted.linear_fit(&points, None).unwrap();
+        assert_eq!((fit.slope, fit.intercept), (2.0, 1.0));
+
+        // La recta cubre todo el rango visible y pasa por los puntos originales.
+        let mut dots = ChartContext::new(24, 6);
+        dots.scatter(&points, None);
+        let fitted_cells = fitted.canvas.render_no_color();
+        let dot_cells = dots.canvas.render_no_color();
+        for (f, d) in fitted_cells.chars().zip(dot_cells.chars()) {
+            assert_eq!(f as u32 & d as u32, d as u32);
+        }
+
+        let rendered = fitted.canvas.render_with_options(false, None);
+        assert!(rendered
+            .lines()
+            .next()
+            .unwrap()
+            .starts_with("y = 2x + 1  R² = 1.000"));
+    }
+
+    #[test]
+    fn simple_moving_average_flattens_an_alternating_series() {
+        let points: Vec<(f64, f64)> = (0..8).map(|i| (i as f64, (i % 2) as f64)).collect();
+        let mut chart = ChartContext::new(8, 2);
+        chart.set_view_range((0.0, 7.0), (0.0, 1.0));
+        chart.moving_average(&points, MovingAverage::Simple(2), None);
+
+        assert_eq!(chart.canvas.render_no_color(), "⠀⣀⣀⣀⣀⣀⣀⣀\n⠀⠀⠀⠀⠀⠀⠀⠀\n");
+    }
+}
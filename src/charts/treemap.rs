@@ -0,0 +1,298 @@
+use super::ChartContext;
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+
+/// Nodo de un treemap jerárquico. El valor de un grupo es la suma de sus hijos.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreemapNode<'a> {
+    pub label: &'a str,
+    pub value: f64,
+    pub color: Option<Color>,
+    pub children: Vec<TreemapNode<'a>>,
+}
+
+impl<'a> TreemapNode<'a> {
+    pub fn leaf(label: &'a str, value: f64, color: Option<Color>) -> Self {
+        Self {
+            label,
+            value,
+            color,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn group(label: &'a str, color: Option<Color>, children: Vec<TreemapNode<'a>>) -> Self {
+        Self {
+            label,
+            value: 0.0,
+            color,
+            children,
+        }
+    }
+
+    /// Valor efectivo: el propio en las hojas y la suma de los hijos en los grupos.
+    /// Valores no finitos o negativos cuentan como cero.
+    pub fn total(&self) -> f64 {
+        if self.children.is_empty() {
+            if self.value.is_finite() {
+                self.value.max(0.0)
+            } else {
+                0.0
+            }
+        } else {
+            self.children.iter().map(TreemapNode::total).sum()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TreemapOptions {
+    pub border_color: Option<Color>,
+    /// Escribe el valor bajo la etiqueta si la caja tiene altura para ello.
+    pub show_values: bool,
+    /// Rellena cada hoja con una trama ligera de su color.
+    pub fill: bool,
+}
+
+/// Rectángulo en celdas, `[x0, x1) x [y0, y1)` con `y` hacia abajo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CellRect {
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+}
+
+struct TreemapBox<'a> {
+    rect: CellRect,
+    node: &'a TreemapNode<'a>,
+}
+
+impl ChartContext {
+    /// Treemap plano: una caja por `(etiqueta, valor, color)`.
+    pub fn treemap(&mut self, items: &[(&str, f64, Option<Color>)], options: &TreemapOptions) {
+        let nodes: Vec<TreemapNode> = items
+            .iter()
+            .map(|&(label, value, color)| TreemapNode::leaf(label, value, color))
+            .collect();
+        self.treemap_nodes(&nodes, options);
+    }
+
+    /// Treemap jerárquico con disposición "squarified" sobre las celdas del lienzo.
+    /// Cada grupo reserva su primera fila para la etiqueta y reparte el resto
+    /// entre sus hijos. Las etiquetas se escriben sobre el borde superior de cada
+    /// caja, recortadas a su ancho.
+    pub fn treemap_nodes(&mut self, nodes: &[TreemapNode], options: &TreemapOptions) {
+        let (width, height) = (self.canvas.width, self.canvas.height);
+        if width == 0 || height == 0 {
+            return;
+        }
+        let mut boxes = Vec::new();
+        let area = CellRect {
+            x0: 0,
+            y0: 0,
+            x1: width,
+            y1: height,
+        };
+        layout_nodes(nodes, area, &mut boxes);
+
+        let h_px = self.canvas.pixel_height() as isize;
+        let w_px = self.canvas.pixel_width() as isize;
+        self.draw_background_overlay(|overlay| {
+            if options.fill {
+                for b in boxes.iter().filter(|b| b.node.children.is_empty()) {
+                    let r = b.rect;
+                    for y in (4 * r.y0 + 2..4 * r.y1 - 1).step_by(4) {
+                        let shift = (y / 4) % 2 * 2;
+                        for x in (2 * r.x0 + 2 + shift..2 * r.x1 - 1).step_by(4) {
+                            overlay.set_pixel_screen(x, y, b.node.color);
+                        }
+                    }
+                }
+            }
+            for b in &boxes {
+                let r = b.rect;
+                let (x0, x1) = (2 * r.x0 as isize, 2 * r.x1 as isize - 1);
+                let (y0, y1) = (4 * r.y0 as isize, 4 * r.y1 as isize - 1);
+                overlay.line_screen(x0, y0, x1, y0, options.border_color);
+                overlay.line_screen(x0, y0, x0, y1, options.border_color);
+            }
+            overlay.line_screen(w_px - 1, 0, w_px - 1, h_px - 1, options.border_color);
+            overlay.line_screen(0, h_px - 1, w_px - 1, h_px - 1, options.border_color);
+        });
+
+        for b in &boxes {
+            let r = b.rect;
+            let inner = (r.x1 - r.x0).saturating_sub(2);
+            if inner == 0 {
+                continue;
+            }
+            let col = r.x0 as isize + 1;
+            let row = (height - 1 - r.y0) as isize;
+            self.text_at_cell(&text::truncate(b.node.label, inner), col, row, b.node.color);
+
+            let is_leaf = b.node.children.is_empty();
+            if options.show_values && is_leaf && r.y1 - r.y0 >= 2 {
+                let text = text::truncate(&AxisScale::format_compact(b.node.total()), inner);
+                self.text_at_cell(&text, col, row - 1, b.node.color);
+            }
+        }
+    }
+}
+
+fn layout_nodes<'a>(nodes: &'a [TreemapNode<'a>], area: CellRect, out: &mut Vec<TreemapBox<'a>>) {
+    let mut order: Vec<&TreemapNode> = nodes.iter().filter(|n| n.total() > 0.0).collect();
+    order.sort_by(|a, b| b.total().total_cmp(&a.total()));
+    let values: Vec<f64> = order.iter().map(|n| n.total()).collect();
+
+    for (node, rect) in order.into_iter().zip(squarify(&values, area)) {
+        if rect.x1 <= rect.x0 || rect.y1 <= rect.y0 {
+            continue;
+        }
+        out.push(TreemapBox { rect, node });
+        if !node.children.is_empty() && rect.y1 - rect.y0 >= 2 {
+            let inner = CellRect {
+                y0: rect.y0 + 1,
+                ..rect
+            };
+            layout_nodes(&node.children, inner, out);
+        }
+    }
+}
+
+/// Algoritmo "squarified" (Bruls, Huizing y van Wijk) sobre `values` ya
+/// ordenados de mayor a menor. Se calcula con las filas al doble de altura que
+/// las columnas, la proporción aproximada de una celda de terminal, y después
+/// se ajusta a bordes de celda.
+fn squarify(values: &[f64], area: CellRect) -> Vec<CellRect> {
+    let total: f64 = values.iter().sum();
+    let (mut x, mut y) = (area.x0 as f64, 2.0 * area.y0 as f64);
+    let (mut w, mut h) = ((area.x1 - area.x0) as f64, 2.0 * (area.y1 - area.y0) as f64);
+    if total <= 0.0 || w <= 0.0 || h <= 0.0 {
+        return Vec::new();
+    }
+    let areas: Vec<f64> = values.iter().map(|v| v / total * w * h).collect();
+
+    let worst = |row: &[f64], side: f64| -> f64 {
+        let sum: f64 = row.iter().sum();
+        let max = row.iter().copied().fold(0.0, f64::max);
+        let min = row.iter().copied().fold(f64::INFINITY, f64::min);
+        (side * side * max / (sum * sum)).max(sum * sum / (side * side * min))
+    };
+
+    let mut rects = Vec::with_capacity(values.len());
+    let mut i = 0;
+    while i < areas.len() {
+        let side = w.min(h);
+        let mut j = i + 1;
+        while j < areas.len() && worst(&areas[i..=j], side) <= worst(&areas[i..j], side) {
+            j += 1;
+        }
+        let row = &areas[i..j];
+        let sum: f64 = row.iter().sum();
+        // La última fila ocupa todo lo que queda para no dejar huecos por redondeo.
+        let last = j == areas.len();
+
+        if w >= h {
+            let thickness = if last { w } else { sum / h };
+            let mut cy = y;
+            for (k, a) in row.iter().enumerate() {
+                let len = if k + 1 == row.len() {
+                    y + h - cy
+                } else {
+                    a / thickness
+                };
+                rects.push((x, cy, thickness, len));
+                cy += len;
+            }
+            x += thickness;
+            w -= thickness;
+        } else {
+            let thickness = if last { h } else { sum / w };
+            let mut cx = x;
+            for (k, a) in row.iter().enumerate() {
+                let len = if k + 1 == row.len() {
+                    x + w - cx
+                } else {
+                    a / thickness
+                };
+                rects.push((cx, y, len, thickness));
+                cx += len;
+            }
+            y += thickness;
+            h -= thickness;
+        }
+        i = j;
+    }
+
+    rects
+        .into_iter()
+        .map(|(x, y, w, h)| CellRect {
+            x0: x.round() as usize,
+            y0: (y / 2.0).round() as usize,
+            x1: (x + w).round() as usize,
+            y1: ((y + h) / 2.0).round() as usize,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{squarify, CellRect, TreemapNode, TreemapOptions};
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn squarify_tiles_the_area_proportionally() {
+        let area = CellRect {
+            x0: 0,
+            y0: 0,
+            x1: 12,
+            y1: 6,
+        };
+        let rects = squarify(&[6.0, 3.0, 2.0, 1.0], area);
+        let cells = |r: &CellRect| (r.x1 - r.x0) * (r.y1 - r.y0);
+        assert_eq!(rects.iter().map(cells).sum::<usize>(), 72);
+        assert_eq!(cells(&rects[0]), 36);
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                let overlap = a.x0 < b.x1 && b.x0 < a.x1 && a.y0 < b.y1 && b.y0 < a.y1;
+                assert!(!overlap, "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn flat_items_get_bordered_boxes_with_labels() {
+        let mut chart = ChartContext::new(10, 2);
+        chart.treemap(
+            &[("alpha", 1.0, None), ("beta", 1.0, None)],
+            &TreemapOptions::default(),
+        );
+
+        assert_eq!(
+            chart.canvas.render_with_options(false, None),
+            "⡏al…⠉⡏be…⢹\n⣇⣀⣀⣀⣀⣇⣀⣀⣀⣸\n"
+        );
+    }
+
+    #[test]
+    fn groups_reserve_a_header_row_for_their_children() {
+        let mut chart = ChartContext::new(12, 4);
+        let tree = [TreemapNode::group(
+            "var",
+            None,
+            vec![
+                TreemapNode::leaf("log", 3.0, None),
+                TreemapNode::leaf("tmp", 1.0, None),
+            ],
+        )];
+        chart.treemap_nodes(&tree, &TreemapOptions::default());
+
+        let rendered = chart.canvas.render_with_options(false, None);
+        let rows: Vec<&str> = rendered.lines().collect();
+        assert!(rows[0].contains("var"));
+        assert!(rows[1].contains("log"));
+        assert!(!rows[0].contains("log"));
+    }
+}
@@ -0,0 +1,312 @@
+use super::{ChartContext, Projection};
+use crate::canvas::BrailleCanvas;
+use crate::colormap::ColorMap;
+use colored::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuiverOptions {
+    pub color: Option<Color>,
+    /// Si está presente, cada flecha se colorea por su módulo (tiene prioridad sobre `color`).
+    pub color_map: Option<ColorMap>,
+    /// Factor sobre la longitud en unidades de datos (1.0 = la flecha mide
+    /// exactamente `(dx, dy)` en el gráfico). Por defecto la flecha más larga
+    /// ocupa el 90% de la separación media entre flechas.
+    pub scale: Option<f64>,
+    /// Longitud máxima de cada lado de la punta, en píxeles.
+    pub head_px: f64,
+}
+
+impl Default for QuiverOptions {
+    fn default() -> Self {
+        Self {
+            color: None,
+            color_map: None,
+            scale: None,
+            head_px: 3.0,
+        }
+    }
+}
+
+impl ChartContext {
+    /// Campo vectorial a partir de muestras `(x, y, dx, dy)`.
+    pub fn quiver(&mut self, vectors: &[(f64, f64, f64, f64)], options: &QuiverOptions) {
+        let valid: Vec<(f64, f64, f64, f64)> = vectors
+            .iter()
+            .copied()
+            .filter(|(x, y, dx, dy)| {
+                x.is_finite() && y.is_finite() && dx.is_finite() && dy.is_finite()
+            })
+            .collect();
+        if valid.is_empty() {
+            return;
+        }
+        let anchors: Vec<(f64, f64)> = valid.iter().map(|&(x, y, _, _)| (x, y)).collect();
+        let (x_range, y_range) = self.resolve_ranges(&anchors);
+        self.quiver_with_ranges(&valid, x_range, y_range, options);
+    }
+
+    /// Campo vectorial de `func(x, y) -> (dx, dy)` muestreado en `nx` x `ny` puntos.
+    pub fn quiver_fn<F>(
+        &mut self,
+        func: F,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        samples: (usize, usize),
+        options: &QuiverOptions,
+    ) where
+        F: Fn(f64, f64) -> (f64, f64),
+    {
+        let (nx, ny) = (samples.0.max(1), samples.1.max(1));
+        let mut vectors = Vec::with_capacity(nx * ny);
+        // Muestras centradas en cada celda para que las flechas no toquen los bordes.
+        for j in 0..ny {
+            let y = y_range.0 + (j as f64 + 0.5) / ny as f64 * (y_range.1 - y_range.0);
+            for i in 0..nx {
+                let x = x_range.0 + (i as f64 + 0.5) / nx as f64 * (x_range.1 - x_range.0);
+                let (dx, dy) = func(x, y);
+                if dx.is_finite() && dy.is_finite() {
+                    vectors.push((x, y, dx, dy));
+                }
+            }
+        }
+        let (x_range, y_range) = self.view_range.unwrap_or((x_range, y_range));
+        self.quiver_with_ranges(&vectors, x_range, y_range, options);
+    }
+
+    /// Líneas de corriente de `func` integradas (punto medio, paso de un píxel)
+    /// hacia delante y hacia atrás desde una rejilla de `seeds.0` x `seeds.1` semillas.
+    pub fn streamlines<F>(
+        &mut self,
+        func: F,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        seeds: (usize, usize),
+        color: Option<Color>,
+    ) where
+        F: Fn(f64, f64) -> (f64, f64),
+    {
+        let (x_range, y_range) = self.view_range.unwrap_or((x_range, y_range));
+        let projection = self.projection(x_range, y_range);
+        let drawable_w = projection
+            .width_px
+            .saturating_sub(1 + projection.left_inset_px)
+            .max(1);
+        let drawable_h = projection
+            .height_px
+            .saturating_sub(1 + projection.bottom_inset_px)
+            .max(1);
+        // Unidades de datos por píxel en cada eje.
+        let unit_x = (x_range.1 - x_range.0) / drawable_w as f64;
+        let unit_y = (y_range.1 - y_range.0) / drawable_h as f64;
+        if unit_x.abs() < 1e-12 || unit_y.abs() < 1e-12 {
+            return;
+        }
+        let max_steps = 2 * (drawable_w + drawable_h);
+        let inside = |x: f64, y: f64| {
+            (x - x_range.0) * (x - x_range.1) <= 0.0 && (y - y_range.0) * (y - y_range.1) <= 0.0
+        };
+        // Dirección normalizada en píxeles, devuelta en unidades de datos.
+        let step = |x: f64, y: f64, sign: f64| -> Option<(f64, f64)> {
+            let (dx, dy) = func(x, y);
+            let (px, py) = (dx / unit_x, dy / unit_y);
+            let len = (px * px + py * py).sqrt();
+            if !len.is_finite() || len < 1e-12 {
+                return None;
+            }
+            Some((sign * px / len * unit_x, sign * py / len * unit_y))
+        };
+
+        let (sx, sy) = (seeds.0.max(1), seeds.1.max(1));
+        self.draw_foreground_overlay(|overlay| {
+            for j in 0..sy {
+                for i in 0..sx {
+                    let seed = (
+                        x_range.0 + (i as f64 + 0.5) / sx as f64 * (x_range.1 - x_range.0),
+                        y_range.0 + (j as f64 + 0.5) / sy as f64 * (y_range.1 - y_range.0),
+                    );
+                    for sign in [1.0, -1.0] {
+                        let (mut x, mut y) = seed;
+                        let mut prev = projection.map(x, y);
+                        for _ in 0..max_steps {
+                            let Some((hx, hy)) = step(x, y, sign) else {
+                                break;
+                            };
+                            let Some((mx, my)) = step(x + hx / 2.0, y + hy / 2.0, sign) else {
+                                break;
+                            };
+                            x += mx;
+                            y += my;
+                            if !inside(x, y) {
+                                break;
+                            }
+                            let next = projection.map(x, y);
+                            if let (Some(a), Some(b)) = (prev, next) {
+                                overlay.line(a.0, a.1, b.0, b.1, color);
+                            }
+                            prev = next;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn quiver_with_ranges(
+        &mut self,
+        vectors: &[(f64, f64, f64, f64)],
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        options: &QuiverOptions,
+    ) {
+        let projection = self.projection(x_range, y_range);
+        // (origen en píxeles, vector en píxeles, módulo en datos)
+        type PixelVector = ((isize, isize), (f64, f64), f64);
+        let pixel_vectors: Vec<PixelVector> = vectors
+            .iter()
+            .filter_map(|&(x, y, dx, dy)| {
+                let (dpx, dpy) = Self::pixel_delta(&projection, x, y, dx, dy)?;
+                let origin = projection.map(x, y)?;
+                Some((origin, (dpx, dpy), (dx * dx + dy * dy).sqrt()))
+            })
+            .collect();
+        if pixel_vectors.is_empty() {
+            return;
+        }
+
+        let max_len_px = pixel_vectors
+            .iter()
+            .map(|(_, (dx, dy), _)| (dx * dx + dy * dy).sqrt())
+            .fold(0.0, f64::max);
+        let max_mag = pixel_vectors.iter().map(|(_, _, m)| *m).fold(0.0, f64::max);
+        let drawable_w = projection
+            .width_px
+            .saturating_sub(1 + projection.left_inset_px) as f64;
+        let drawable_h = projection
+            .height_px
+            .saturating_sub(1 + projection.bottom_inset_px) as f64;
+        let spacing = (drawable_w * drawable_h / pixel_vectors.len() as f64).sqrt();
+        let scale = options.scale.unwrap_or(if max_len_px > 1e-9 {
+            0.9 * spacing / max_len_px
+        } else {
+            0.0
+        });
+
+        let head_px = options.head_px.max(0.0);
+        let color_map = options.color_map;
+        let base_color = options.color;
+        self.draw_foreground_overlay(|overlay| {
+            for &(origin, (dx, dy), magnitude) in &pixel_vectors {
+                let color = match color_map {
+                    Some(map) if max_mag > 1e-12 => Some(map.sample(magnitude / max_mag)),
+                    _ => base_color,
+                };
+                Self::draw_arrow(overlay, origin, (dx * scale, dy * scale), head_px, color);
+            }
+        });
+    }
+
+    /// Desplazamiento en píxeles de `(dx, dy)` aplicado en `(x, y)`, respetando la
+    /// escala de cada eje.
+    fn pixel_delta(
+        projection: &Projection,
+        x: f64,
+        y: f64,
+        dx: f64,
+        dy: f64,
+    ) -> Option<(f64, f64)> {
+        let a = projection.map_f64(x, y)?;
+        let b = projection.map_f64(x + dx, y + dy)?;
+        Some((b.0 - a.0, b.1 - a.1))
+    }
+
+    fn draw_arrow(
+        overlay: &mut BrailleCanvas,
+        origin: (isize, isize),
+        delta: (f64, f64),
+        head_px: f64,
+        color: Option<Color>,
+    ) {
+        let len = (delta.0 * delta.0 + delta.1 * delta.1).sqrt();
+        if len < 0.5 {
+            overlay.line(origin.0, origin.1, origin.0, origin.1, color);
+            return;
+        }
+        let tip = (
+            (origin.0 as f64 + delta.0).round() as isize,
+            (origin.1 as f64 + delta.1).round() as isize,
+        );
+        overlay.line(origin.0, origin.1, tip.0, tip.1, color);
+
+        let head = head_px.min(len * 0.4);
+        if head < 1.0 {
+            return;
+        }
+        let (ux, uy) = (delta.0 / len, delta.1 / len);
+        let (sin, cos) = 0.45f64.sin_cos();
+        for side in [1.0, -1.0] {
+            // Vector hacia atrás rotado ±~25º.
+            let bx = -ux * cos + side * uy * sin;
+            let by = -uy * cos - side * ux * sin;
+            let end = (
+                (tip.0 as f64 + bx * head).round() as isize,
+                (tip.1 as f64 + by * head).round() as isize,
+            );
+            overlay.line(tip.0, tip.1, end.0, end.1, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuiverOptions;
+    use crate::charts::ChartContext;
+    use crate::colormap::ColorMap;
+    use colored::Color;
+
+    #[test]
+    fn single_arrow_points_right_with_a_head() {
+        let mut chart = ChartContext::new(6, 2);
+        chart.set_view_range((0.0, 1.0), (0.0, 1.0));
+        chart.quiver(
+            &[(0.0, 0.5, 1.0, 0.0)],
+            &QuiverOptions {
+                scale: Some(0.5),
+                ..QuiverOptions::default()
+            },
+        );
+
+        assert_eq!(chart.canvas.render_no_color(), "⣀⣀⣤⡀⠀⠀\n⠀⠀⠉⠀⠀⠀\n");
+    }
+
+    #[test]
+    fn color_map_paints_longest_vector_with_the_top_color() {
+        let mut chart = ChartContext::new(10, 4);
+        chart.quiver(
+            &[(0.0, 0.0, 0.1, 0.0), (1.0, 1.0, 1.0, 0.0)],
+            &QuiverOptions {
+                color_map: Some(ColorMap::Grayscale),
+                ..QuiverOptions::default()
+            },
+        );
+
+        let rendered = chart.canvas.render();
+        let top = ColorMap::Grayscale.sample(1.0);
+        assert_eq!(
+            top,
+            Color::TrueColor {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert!(rendered.contains("\x1b[38;2;255;255;255m"));
+    }
+
+    #[test]
+    fn streamlines_of_a_uniform_field_are_horizontal() {
+        let mut chart = ChartContext::new(6, 2);
+        chart.streamlines(|_, _| (1.0, 0.0), (0.0, 1.0), (0.0, 1.0), (1, 1), None);
+
+        assert_eq!(chart.canvas.render_no_color(), "⣀⣀⣀⣀⣀⣀\n⠀⠀⠀⠀⠀⠀\n");
+    }
+}
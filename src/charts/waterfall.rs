@@ -0,0 +1,184 @@
+use super::ChartContext;
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaterfallOptions {
+    /// Separación en píxeles entre barras.
+    pub gap_px: usize,
+    /// Añade al final una barra con el total acumulado desde cero.
+    pub show_total: bool,
+    pub total_label: &'static str,
+    /// Une el final de cada barra con el inicio de la siguiente.
+    pub connectors: bool,
+    pub increase_color: Option<Color>,
+    pub decrease_color: Option<Color>,
+    pub total_color: Option<Color>,
+    pub connector_color: Option<Color>,
+    pub label_color: Option<Color>,
+}
+
+impl Default for WaterfallOptions {
+    fn default() -> Self {
+        Self {
+            gap_px: 2,
+            show_total: true,
+            total_label: "Total",
+            connectors: true,
+            increase_color: None,
+            decrease_color: None,
+            total_color: None,
+            connector_color: None,
+            label_color: None,
+        }
+    }
+}
+
+impl WaterfallOptions {
+    pub fn with_colors(
+        mut self,
+        increase: Option<Color>,
+        decrease: Option<Color>,
+        total: Option<Color>,
+    ) -> Self {
+        self.increase_color = increase;
+        self.decrease_color = decrease;
+        self.total_color = total;
+        self
+    }
+}
+
+impl ChartContext {
+    /// Cascada: cada `(etiqueta, delta)` es una barra flotante que parte del total
+    /// acumulado hasta ese paso. El eje de valores es siempre lineal e incluye el
+    /// cero (o el rango y fijado con `set_view_range`). Las etiquetas, si hay
+    /// alguna no vacía, van en la fila inferior.
+    pub fn waterfall_chart(&mut self, steps: &[(&str, f64)], options: &WaterfallOptions) {
+        // (etiqueta, desde, hasta, color)
+        let mut bars: Vec<(&str, f64, f64, Option<Color>)> = Vec::with_capacity(steps.len() + 1);
+        let mut total = 0.0;
+        for &(label, delta) in steps.iter().filter(|(_, d)| d.is_finite()) {
+            let color = if delta >= 0.0 {
+                options.increase_color
+            } else {
+                options.decrease_color
+            };
+            bars.push((label, total, total + delta, color));
+            total += delta;
+        }
+        if bars.is_empty() {
+            return;
+        }
+        if options.show_total {
+            bars.push((options.total_label, 0.0, total, options.total_color));
+        }
+
+        let (min_v, max_v) = match self.view_range {
+            Some((_, y_range)) => y_range,
+            None => {
+                let (lo, hi) = bars.iter().fold((0.0f64, 0.0f64), |(lo, hi), b| {
+                    (lo.min(b.1.min(b.2)), hi.max(b.1.max(b.2)))
+                });
+                Self::expand_range(lo, hi, 0.05, AxisScale::Linear)
+            }
+        };
+
+        let has_labels = bars.iter().any(|b| !b.0.is_empty());
+        let w_px = self.canvas.pixel_width();
+        let h_px = self.canvas.pixel_height();
+        let (left_inset_px, bottom_inset_px) = self.canvas.plot_insets();
+        let val_origin = (bottom_inset_px + if has_labels { 4 } else { 0 }) as f64;
+        let val_len = (h_px as f64 - val_origin).max(1.0);
+        let span = (max_v - min_v).max(1e-9);
+        let value_to_px = |v: f64| (val_origin + ((v - min_v) / span * val_len).round()) as isize;
+
+        let slot = w_px.saturating_sub(left_inset_px) as f64 / bars.len() as f64;
+        let half_gap = options.gap_px as f64 / 2.0;
+        let columns: Vec<(isize, isize)> = (0..bars.len())
+            .map(|i| {
+                let start = (left_inset_px as f64 + slot * i as f64 + half_gap).round() as isize;
+                let end =
+                    (left_inset_px as f64 + slot * (i + 1) as f64 - half_gap).round() as isize - 1;
+                (start, end.max(start))
+            })
+            .collect();
+
+        self.draw_foreground_overlay(|overlay| {
+            // Extremo (en píxeles) donde acaba cada barra, para los conectores.
+            let mut previous_edge: Option<(isize, isize)> = None;
+            for (bar, &(start, end)) in bars.iter().zip(&columns) {
+                let (_, from, to, color) = *bar;
+                let (v0, v1) = (value_to_px(from), value_to_px(to));
+                // Intervalo semiabierto, como en `bar_chart_with`; un delta nulo
+                // queda como una línea de un píxel.
+                let (lo, hi) = (v0.min(v1), (v0.max(v1) - 1).max(v0.min(v1)));
+                let top = h_px as isize - 1 - hi;
+                overlay.rect_filled(
+                    start,
+                    top,
+                    (end - start + 1) as usize,
+                    (hi - lo + 1) as usize,
+                    color,
+                );
+
+                if let (true, Some((x, y))) = (options.connectors, previous_edge) {
+                    if x < start {
+                        overlay.line(x, y, start - 1, y, options.connector_color);
+                    }
+                }
+                let edge_y = if to >= from { hi } else { lo };
+                previous_edge = Some((end + 1, edge_y));
+            }
+        });
+
+        if has_labels {
+            let row = (bottom_inset_px / 4) as isize;
+            let max_len = (slot / 2.0).floor() as usize;
+            for (bar, &(start, end)) in bars.iter().zip(&columns) {
+                let text = text::truncate(bar.0, max_len.max(1));
+                let len = text::display_width(&text) as isize;
+                let center_col = (start + end) / 4;
+                let color = options.label_color.or(bar.3);
+                self.text_at_cell(&text, center_col - (len - 1) / 2, row, color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WaterfallOptions;
+    use crate::charts::ChartContext;
+
+    #[test]
+    fn deltas_float_on_the_running_total() {
+        let mut chart = ChartContext::new(8, 2);
+        chart.set_view_range((0.0, 1.0), (0.0, 8.0));
+        let options = WaterfallOptions {
+            gap_px: 0,
+            connectors: false,
+            total_label: "",
+            ..WaterfallOptions::default()
+        };
+        chart.waterfall_chart(&[("", 4.0), ("", 2.0), ("", -4.0)], &options);
+
+        // +4 desde 0, +2 desde 4, -4 desde 6 y el total (2) desde 0.
+        assert_eq!(chart.canvas.render_no_color(), "⠀⠀⣤⣤⣤⣤⠀⠀\n⣿⣿⠀⠀⠛⠛⣤⣤\n");
+    }
+
+    #[test]
+    fn connectors_and_labels_are_drawn() {
+        let mut chart = ChartContext::new(12, 3);
+        chart.waterfall_chart(&[("in", 5.0), ("out", -2.0)], &WaterfallOptions::default());
+
+        let rendered = chart.canvas.render_with_options(false, None);
+        let labels = rendered.lines().last().unwrap();
+        assert!(labels.contains("in"));
+        assert!(labels.contains("out"));
+        assert!(labels.contains('T'));
+        // Conector entre la primera y la segunda barra, a la altura de 5.
+        let top: Vec<char> = rendered.lines().next().unwrap().chars().collect();
+        assert_ne!(top[4], '⠀');
+    }
+}
@@ -0,0 +1,45 @@
+use colored::Color;
+
+/// Paletas continuas para colorear por magnitud; `sample(0.0)` es el extremo bajo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMap {
+    Viridis,
+    /// Negro -> rojo -> amarillo -> blanco.
+    Heat,
+    /// Azul -> blanco -> rojo, útil para valores con signo.
+    CoolWarm,
+    Grayscale,
+}
+
+impl ColorMap {
+    pub fn sample(self, t: f64) -> Color {
+        let t = if t.is_finite() {
+            t.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let stops: &[(u8, u8, u8)] = match self {
+            Self::Viridis => &[
+                (68, 1, 84),
+                (59, 82, 139),
+                (33, 145, 140),
+                (94, 201, 98),
+                (253, 231, 37),
+            ],
+            Self::Heat => &[(0, 0, 0), (200, 30, 0), (255, 210, 0), (255, 255, 255)],
+            Self::CoolWarm => &[(59, 76, 192), (221, 221, 221), (180, 4, 38)],
+            Self::Grayscale => &[(40, 40, 40), (255, 255, 255)],
+        };
+
+        let pos = t * (stops.len() - 1) as f64;
+        let i = (pos.floor() as usize).min(stops.len() - 2);
+        let f = pos - i as f64;
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+        let (a, b) = (stops[i], stops[i + 1]);
+        Color::TrueColor {
+            r: lerp(a.0, b.0),
+            g: lerp(a.1, b.1),
+            b: lerp(a.2, b.2),
+        }
+    }
+}
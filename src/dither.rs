@@ -0,0 +1,298 @@
+use crate::canvas::BrailleCanvas;
+use colored::Color;
+
+/// Cómo pasar de intensidades continuas a puntos Braille encendidos o apagados.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Dither {
+    /// Umbral fijo: se enciende todo lo que supere `0.5`. Sin trama, con bandas.
+    Threshold,
+    /// Dithering ordenado con la matriz de Bayer 4x4: trama regular y estable
+    /// entre fotogramas, buena para animaciones.
+    #[default]
+    Bayer,
+    /// Difusión de error de Floyd–Steinberg: más detalle, pero el patrón cambia
+    /// con cualquier variación de la imagen.
+    FloydSteinberg,
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Buffer de intensidades en `[0, 1]` por sub-píxel, del mismo tamaño que un
+/// `BrailleCanvas` de `width x height` celdas. Se pinta con intensidades y se
+/// vuelca al lienzo con `resolve`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntensityBuffer {
+    width_px: usize,
+    height_px: usize,
+    data: Vec<f64>,
+}
+
+impl IntensityBuffer {
+    pub fn new(width: usize, height: usize) -> Self {
+        let (width_px, height_px) = (width * 2, height * 4);
+        Self {
+            width_px,
+            height_px,
+            data: vec![0.0; width_px * height_px],
+        }
+    }
+
+    pub fn pixel_width(&self) -> usize {
+        self.width_px
+    }
+
+    pub fn pixel_height(&self) -> usize {
+        self.height_px
+    }
+
+    pub fn clear(&mut self) {
+        self.data.fill(0.0);
+    }
+
+    /// Intensidad del píxel `(x, y)` en coordenadas cartesianas.
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        if y >= self.height_px {
+            return 0.0;
+        }
+        self.get_screen(x, self.height_px - 1 - y)
+    }
+
+    pub fn get_screen(&self, x: usize, y: usize) -> f64 {
+        if x >= self.width_px || y >= self.height_px {
+            return 0.0;
+        }
+        self.data[y * self.width_px + x]
+    }
+
+    /// Fija la intensidad de `(x, y)` (cartesianas), recortada a `[0, 1]`.
+    pub fn set(&mut self, x: usize, y: usize, intensity: f64) {
+        if y < self.height_px {
+            self.set_screen(x, self.height_px - 1 - y, intensity);
+        }
+    }
+
+    pub fn set_screen(&mut self, x: usize, y: usize, intensity: f64) {
+        if x < self.width_px && y < self.height_px && !intensity.is_nan() {
+            self.data[y * self.width_px + x] = intensity.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Como `set`, pero se queda con la mayor de las dos intensidades, para que
+    /// los trazos que se cruzan no se oscurezcan unos a otros.
+    pub fn blend(&mut self, x: usize, y: usize, intensity: f64) {
+        if y < self.height_px {
+            self.blend_screen(x, self.height_px - 1 - y, intensity);
+        }
+    }
+
+    pub fn blend_screen(&mut self, x: usize, y: usize, intensity: f64) {
+        let current = self.get_screen(x, y);
+        if intensity > current {
+            self.set_screen(x, y, intensity);
+        }
+    }
+
+    /// Línea antialias de Xiaolin Wu entre puntos en píxeles cartesianos, con
+    /// intensidad máxima `intensity`.
+    pub fn line_aa(&mut self, x0: f64, y0: f64, x1: f64, y1: f64, intensity: f64) {
+        self.wu_line(x0, y0, x1, y1, intensity, true);
+    }
+
+    pub fn line_aa_screen(&mut self, x0: f64, y0: f64, x1: f64, y1: f64, intensity: f64) {
+        self.wu_line(x0, y0, x1, y1, intensity, false);
+    }
+
+    fn plot(&mut self, x: f64, y: f64, intensity: f64, cartesian: bool) {
+        if x < 0.0 || y < 0.0 || intensity <= 0.0 {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if cartesian {
+            self.blend(x, y, intensity);
+        } else {
+            self.blend_screen(x, y, intensity);
+        }
+    }
+
+    fn wu_line(
+        &mut self,
+        mut x0: f64,
+        mut y0: f64,
+        mut x1: f64,
+        mut y1: f64,
+        intensity: f64,
+        cartesian: bool,
+    ) {
+        if ![x0, y0, x1, y1].iter().all(|v| v.is_finite()) {
+            return;
+        }
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            (x0, y0, x1, y1) = (y0, x0, y1, x1);
+        }
+        if x0 > x1 {
+            (x0, y0, x1, y1) = (x1, y1, x0, y0);
+        }
+        let dx = x1 - x0;
+        let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+
+        // Recorre la dirección dominante y reparte cada muestra entre los dos
+        // píxeles que la rodean en la otra dirección.
+        let put = |buffer: &mut Self, major: f64, minor: f64, coverage: f64| {
+            let value = coverage * intensity;
+            if steep {
+                buffer.plot(minor, major, value, cartesian);
+            } else {
+                buffer.plot(major, minor, value, cartesian);
+            }
+        };
+
+        // Recorte al buffer antes de recorrer: en la dirección dominante, y en la
+        // otra solo el tramo cuya muestra (o su vecino de abajo) cae dentro.
+        let (major_len, minor_len) = if steep {
+            (self.height_px as f64, self.width_px as f64)
+        } else {
+            (self.width_px as f64, self.height_px as f64)
+        };
+        let mut x_start = x0.round().max(0.0);
+        let mut x_end = x1.round().min(major_len - 1.0);
+        if gradient == 0.0 {
+            if y0 < -1.0 || y0 >= minor_len {
+                return;
+            }
+        } else {
+            let a = x0 + (-1.0 - y0) / gradient;
+            let b = x0 + (minor_len - y0) / gradient;
+            x_start = x_start.max(a.min(b).floor());
+            x_end = x_end.min(a.max(b).ceil());
+        }
+        if x_start > x_end {
+            return;
+        }
+
+        // Contador entero: tras el recorte son como mucho unos pocos miles de
+        // pasos, y cada `y` se calcula desde el origen sin acumular error.
+        let steps = (x_end - x_start) as usize;
+        for i in 0..=steps {
+            let x = x_start + i as f64;
+            let y = y0 + gradient * (x - x0);
+            let base = y.floor();
+            let frac = y - base;
+            put(self, x, base, 1.0 - frac);
+            put(self, x, base + 1.0, frac);
+        }
+    }
+
+    /// Vuelca el buffer en `canvas` encendiendo los sub-píxeles que decida
+    /// `dither`. Solo enciende: lo que ya hubiera en el lienzo se conserva.
+    pub fn resolve(&self, canvas: &mut BrailleCanvas, dither: Dither, color: Option<Color>) {
+        let (w, h) = (
+            self.width_px.min(canvas.pixel_width()),
+            self.height_px.min(canvas.pixel_height()),
+        );
+        match dither {
+            Dither::Threshold | Dither::Bayer => {
+                for y in 0..h {
+                    for x in 0..w {
+                        let threshold = match dither {
+                            Dither::Bayer => (BAYER_4X4[y % 4][x % 4] as f64 + 0.5) / 16.0,
+                            _ => 0.5,
+                        };
+                        if self.get_screen(x, y) > threshold {
+                            canvas.set_pixel_screen(x, y, color);
+                        }
+                    }
+                }
+            }
+            Dither::FloydSteinberg => {
+                let mut values = self.data.clone();
+                let stride = self.width_px;
+                for y in 0..h {
+                    for x in 0..w {
+                        let old = values[y * stride + x];
+                        let new = if old >= 0.5 { 1.0 } else { 0.0 };
+                        if new > 0.0 {
+                            canvas.set_pixel_screen(x, y, color);
+                        }
+                        let error = old - new;
+                        let mut spread = |dx: isize, dy: usize, weight: f64| {
+                            let nx = x as isize + dx;
+                            let ny = y + dy;
+                            if nx >= 0 && (nx as usize) < w && ny < h {
+                                values[ny * stride + nx as usize] += error * weight;
+                            }
+                        };
+                        spread(1, 0, 7.0 / 16.0);
+                        spread(-1, 1, 3.0 / 16.0);
+                        spread(0, 1, 5.0 / 16.0);
+                        spread(1, 1, 1.0 / 16.0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dither, IntensityBuffer};
+    use crate::canvas::BrailleCanvas;
+
+    fn dots(canvas: &BrailleCanvas) -> u32 {
+        canvas
+            .render_no_color()
+            .chars()
+            .filter(|c| *c != '\n')
+            .map(|c| (c as u32 - 0x2800).count_ones())
+            .sum()
+    }
+
+    #[test]
+    fn both_dithers_preserve_the_average_intensity() {
+        let mut buffer = IntensityBuffer::new(8, 4);
+        for y in 0..buffer.pixel_height() {
+            for x in 0..buffer.pixel_width() {
+                buffer.set(x, y, 0.25);
+            }
+        }
+
+        for dither in [Dither::Bayer, Dither::FloydSteinberg] {
+            let mut canvas = BrailleCanvas::new(8, 4);
+            buffer.resolve(&mut canvas, dither, None);
+            let on = dots(&canvas) as i32;
+            // La difusión pierde algo de error por los bordes.
+            assert!((on - 64).abs() <= 6, "{dither:?}: {on}");
+        }
+
+        let mut canvas = BrailleCanvas::new(8, 4);
+        buffer.resolve(&mut canvas, Dither::Threshold, None);
+        assert_eq!(dots(&canvas), 0);
+    }
+
+    #[test]
+    fn wu_lines_split_coverage_between_neighbouring_rows() {
+        let mut buffer = IntensityBuffer::new(4, 2);
+        buffer.line_aa(0.0, 2.0, 7.0, 2.0, 1.0);
+        assert_eq!(buffer.get(3, 2), 1.0);
+        assert_eq!(buffer.get(3, 3), 0.0);
+
+        // A media altura entre dos filas, cada una recibe la mitad.
+        let mut half = IntensityBuffer::new(4, 2);
+        half.line_aa(0.0, 2.5, 7.0, 2.5, 1.0);
+        assert_eq!((half.get(3, 2), half.get(3, 3)), (0.5, 0.5));
+    }
+
+    #[test]
+    fn far_endpoints_are_clipped_before_walking_the_line() {
+        // Sin recorte estos trazos recorrerían 1e12 píxeles (o no terminarían).
+        let mut buffer = IntensityBuffer::new(4, 2);
+        buffer.line_aa(0.0, 0.0, 1e12, 1.0, 1.0);
+        buffer.line_aa(0.0, 5.0, 1e17, 5.0, 1.0);
+        buffer.line_aa(-1e9, -1e9, 1e9, 1e9, 1.0);
+        buffer.line_aa(-1e15, 50.0, 1e15, 60.0, 1.0);
+        assert!(buffer.get(7, 0) > 0.99);
+        assert_eq!(buffer.get(7, 5), 1.0);
+        assert_eq!(buffer.get(3, 3), 1.0);
+        assert_eq!(buffer.get(0, 7), 0.0);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::canvas::BrailleCanvas;
+use colored::Color;
+
+/// Fuente de mapa de bits que se dibuja en los sub-píxeles del lienzo, no en la
+/// capa de texto: se puede colocar con precisión de píxel y escalar.
+///
+/// Solo incluye dígitos, mayúsculas y algo de puntuación; las minúsculas se
+/// dibujan como mayúsculas y el resto de caracteres como `?`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitmapFont {
+    /// 3x5 píxeles: cabe en poco más de una celda de alto.
+    #[default]
+    Font3x5,
+    /// 5x7 píxeles: más legible, para cifras grandes en paneles.
+    Font5x7,
+}
+
+impl BitmapFont {
+    /// Tamaño de un glifo en píxeles, sin escalar.
+    pub fn glyph_size(self) -> (usize, usize) {
+        match self {
+            Self::Font3x5 => (3, 5),
+            Self::Font5x7 => (5, 7),
+        }
+    }
+
+    /// Ancho en píxeles de la línea más larga de `text`, con un píxel de
+    /// separación entre glifos (que no se cuenta tras el último).
+    pub fn text_width(self, text: &str, scale: usize) -> usize {
+        let (w, _) = self.glyph_size();
+        text.lines()
+            .map(|line| line.chars().count())
+            .max()
+            .map_or(0, |n| (n * (w + 1)).saturating_sub(1) * scale)
+    }
+
+    /// Alto en píxeles de `text`, con un píxel de interlineado.
+    pub fn text_height(self, text: &str, scale: usize) -> usize {
+        let (_, h) = self.glyph_size();
+        let lines = text.lines().count().max(1);
+        (lines * (h + 1) - 1) * scale
+    }
+
+    /// Filas del glifo, con el bit más alto a la izquierda.
+    fn glyph(self, c: char) -> &'static [u8] {
+        let c = c.to_ascii_uppercase();
+        let lookup = |table: &'static [(char, &'static [u8])]| {
+            table
+                .iter()
+                .find(|(ch, _)| *ch == c)
+                .or_else(|| table.iter().find(|(ch, _)| *ch == '?'))
+                .map_or(&[][..], |(_, rows)| rows)
+        };
+        match self {
+            Self::Font3x5 => lookup(FONT_3X5),
+            Self::Font5x7 => lookup(FONT_5X7),
+        }
+    }
+}
+
+impl BrailleCanvas {
+    /// Escribe `text` con `font` a escala `scale`; `(x, y)` es la esquina superior
+    /// izquierda en píxeles cartesianos. `\n` empieza una línea nueva debajo.
+    pub fn text_bitmap(
+        &mut self,
+        text: &str,
+        x: isize,
+        y: isize,
+        font: BitmapFont,
+        scale: usize,
+        color: Option<Color>,
+    ) {
+        let top = self.pixel_height() as isize - 1 - y;
+        self.text_bitmap_screen(text, x, top, font, scale, color);
+    }
+
+    /// Como `text_bitmap`, con `(x, y)` en coordenadas de pantalla.
+    pub fn text_bitmap_screen(
+        &mut self,
+        text: &str,
+        x: isize,
+        y: isize,
+        font: BitmapFont,
+        scale: usize,
+        color: Option<Color>,
+    ) {
+        let scale = scale.max(1) as isize;
+        let (w, h) = font.glyph_size();
+        let (advance, line_height) = ((w as isize + 1) * scale, (h as isize + 1) * scale);
+        let (max_x, max_y) = (self.pixel_width() as isize, self.pixel_height() as isize);
+
+        for (line_no, line) in text.lines().enumerate() {
+            let gy = y + line_no as isize * line_height;
+            for (i, c) in line.chars().enumerate() {
+                let gx = x + i as isize * advance;
+                for (row, bits) in font.glyph(c).iter().enumerate() {
+                    for col in 0..w {
+                        if bits >> (w - 1 - col) & 1 == 0 {
+                            continue;
+                        }
+                        let (px, py) = (gx + col as isize * scale, gy + row as isize * scale);
+                        for dy in 0..scale {
+                            for dx in 0..scale {
+                                let (sx, sy) = (px + dx, py + dy);
+                                if sx >= 0 && sy >= 0 && sx < max_x && sy < max_y {
+                                    self.set_pixel_screen(sx as usize, sy as usize, color);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+const FONT_3X5: &[(char, &[u8])] = &[
+    ('0', &[0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', &[0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', &[0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', &[0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', &[0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', &[0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', &[0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', &[0b111, 0b001, 0b001, 0b001, 0b001]),
+    ('8', &[0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', &[0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('A', &[0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', &[0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', &[0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', &[0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', &[0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', &[0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', &[0b011, 0b100, 0b101, 0b101, 0b011]),
+    ('H', &[0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', &[0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', &[0b001, 0b001, 0b001, 0b101, 0b010]),
+    ('K', &[0b101, 0b101, 0b110, 0b101, 0b101]),
+    ('L', &[0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', &[0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', &[0b110, 0b101, 0b101, 0b101, 0b101]),
+    ('O', &[0b010, 0b101, 0b101, 0b101, 0b010]),
+    ('P', &[0b110, 0b101, 0b110, 0b100, 0b100]),
+    ('Q', &[0b010, 0b101, 0b101, 0b110, 0b011]),
+    ('R', &[0b110, 0b101, 0b110, 0b101, 0b101]),
+    ('S', &[0b011, 0b100, 0b010, 0b001, 0b110]),
+    ('T', &[0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', &[0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', &[0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', &[0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', &[0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', &[0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', &[0b111, 0b001, 0b010, 0b100, 0b111]),
+    (' ', &[0b000, 0b000, 0b000, 0b000, 0b000]),
+    ('.', &[0b000, 0b000, 0b000, 0b000, 0b010]),
+    (',', &[0b000, 0b000, 0b000, 0b010, 0b100]),
+    (':', &[0b000, 0b010, 0b000, 0b010, 0b000]),
+    ('-', &[0b000, 0b000, 0b111, 0b000, 0b000]),
+    ('+', &[0b000, 0b010, 0b111, 0b010, 0b000]),
+    ('=', &[0b000, 0b111, 0b000, 0b111, 0b000]),
+    ('%', &[0b101, 0b001, 0b010, 0b100, 0b101]),
+    ('/', &[0b001, 0b001, 0b010, 0b100, 0b100]),
+    ('(', &[0b010, 0b100, 0b100, 0b100, 0b010]),
+    (')', &[0b010, 0b001, 0b001, 0b001, 0b010]),
+    ('!', &[0b010, 0b010, 0b010, 0b000, 0b010]),
+    ('?', &[0b110, 0b001, 0b010, 0b000, 0b010]),
+];
+
+#[rustfmt::skip]
+const FONT_5X7: &[(char, &[u8])] = &[
+    ('0', &[0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', &[0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', &[0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', &[0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', &[0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', &[0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', &[0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', &[0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', &[0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', &[0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    ('A', &[0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', &[0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', &[0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', &[0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100]),
+    ('E', &[0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', &[0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', &[0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', &[0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', &[0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', &[0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', &[0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', &[0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', &[0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', &[0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', &[0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', &[0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', &[0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', &[0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', &[0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', &[0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', &[0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', &[0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', &[0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', &[0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', &[0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100]),
+    ('Z', &[0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+    (' ', &[0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('.', &[0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+    (',', &[0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000]),
+    (':', &[0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000]),
+    ('-', &[0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+    ('+', &[0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000]),
+    ('=', &[0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000]),
+    ('%', &[0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011]),
+    ('/', &[0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000]),
+    ('(', &[0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010]),
+    (')', &[0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000]),
+    ('!', &[0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100]),
+    ('?', &[0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::BitmapFont;
+    use crate::canvas::BrailleCanvas;
+
+    #[test]
+    fn small_digits_fit_in_two_cells() {
+        let mut canvas = BrailleCanvas::new(2, 2);
+        canvas.text_bitmap_screen("1", 0, 0, BitmapFont::Font3x5, 1, None);
+        assert_eq!(canvas.render_no_color(), "⢺⠀\n⠉⠁\n");
+    }
+
+    #[test]
+    fn scale_multiplies_every_font_pixel() {
+        let font = BitmapFont::Font5x7;
+        assert_eq!(font.text_width("42", 1), 11);
+        assert_eq!(font.text_width("42", 3), 33);
+        assert_eq!(font.text_height("a\nb", 2), 30);
+
+        // A escala 2 cada píxel es un bloque 2x2: la barra del '-' ocupa 10x2.
+        let mut canvas = BrailleCanvas::new(5, 4);
+        canvas.text_bitmap_screen("-", 0, 0, font, 2, None);
+        assert_eq!(canvas.render_no_color(), "⠀⠀⠀⠀⠀\n⣤⣤⣤⣤⣤\n⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀\n");
+    }
+}
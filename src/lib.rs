@@ -1,8 +1,29 @@
 pub mod canvas;
 pub mod charts;
+pub mod colormap;
+pub mod dither;
+pub mod font;
 pub mod prelude;
 pub mod scale;
+pub mod sparkline;
+pub mod stats;
+pub mod text;
+pub mod widgets;
 
-pub use canvas::{BrailleCanvas, ColorBlend};
-pub use charts::ChartContext;
+pub use canvas::{
+    BrailleCanvas, ColorBlend, Connectivity, DashPattern, FillPattern, FillRule, LineCap, LineStyle,
+};
+pub use charts::{
+    BarChartOptions, BarLabels, BarLayout, BarOrientation, ChartContext, ErrorBar, FunnelOptions,
+    GanttOptions, GanttRow, LineInterpolation, Marker, MarkerShape, MovingAverage, PieChartOptions,
+    PieLabels, QqReference, QuiverOptions, RadarOptions, SeriesPatterns, TreemapNode,
+    TreemapOptions, ViolinOptions, WaterfallOptions,
+};
+pub use colormap::ColorMap;
+pub use dither::{Dither, IntensityBuffer};
+pub use font::BitmapFont;
 pub use scale::AxisScale;
+pub use sparkline::{Sparkline, SparklineStyle};
+pub use stats::{Bandwidth, LinearFit, PolynomialFit};
+pub use text::{HAlign, VAlign};
+pub use widgets::{BulletChart, Gauge, ProgressBar};
@@ -1,3 +1,17 @@
-pub use crate::canvas::{BrailleCanvas, ColorBlend};
-pub use crate::charts::ChartContext;
+pub use crate::canvas::{
+    BrailleCanvas, ColorBlend, Connectivity, DashPattern, FillPattern, FillRule, LineCap, LineStyle,
+};
+pub use crate::charts::{
+    BarChartOptions, BarLabels, BarLayout, BarOrientation, ChartContext, ErrorBar, FunnelOptions,
+    GanttOptions, GanttRow, LineInterpolation, Marker, MarkerShape, MovingAverage, PieChartOptions,
+    PieLabels, QqReference, QuiverOptions, RadarOptions, SeriesPatterns, TreemapNode,
+    TreemapOptions, ViolinOptions, WaterfallOptions,
+};
+pub use crate::colormap::ColorMap;
+pub use crate::dither::{Dither, IntensityBuffer};
+pub use crate::font::BitmapFont;
 pub use crate::scale::AxisScale;
+pub use crate::sparkline::{Sparkline, SparklineStyle};
+pub use crate::stats::{Bandwidth, LinearFit, PolynomialFit};
+pub use crate::text::{HAlign, VAlign};
+pub use crate::widgets::{BulletChart, Gauge, ProgressBar};
@@ -132,7 +132,7 @@ impl AxisScale {
         Self::format_compact(value)
     }
 
-    fn format_compact(value: f64) -> String {
+    pub(crate) fn format_compact(value: f64) -> String {
         let abs = value.abs();
         let raw = if abs >= 1000.0 || (abs > 0.0 && abs < 0.1) {
             format!("{:.1e}", value)
@@ -0,0 +1,244 @@
+use crate::canvas::BrailleCanvas;
+use colored::Color;
+use std::fmt::{self, Write};
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SparklineStyle {
+    /// Dos muestras por carácter y 4 niveles por fila, unidas con líneas.
+    Braille,
+    /// Una muestra por carácter con `▁▂▃▄▅▆▇█` (8 niveles por fila).
+    Blocks,
+}
+
+/// Tendencia compacta sin ejes, pensada para incrustarse en tablas o logs.
+///
+/// A diferencia de `ChartContext` no añade márgenes al rango: el mínimo de la
+/// serie toca la base y el máximo el techo, aprovechando toda la resolución.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sparkline {
+    pub style: SparklineStyle,
+    /// Altura en filas de texto.
+    pub rows: usize,
+    /// Ancho máximo en caracteres; las series más largas se promedian por tramos.
+    pub max_width: Option<usize>,
+    /// Rango fijo `(min, max)`; por defecto el de los datos.
+    pub range: Option<(f64, f64)>,
+    /// Rellena el área bajo la curva (solo `Braille`).
+    pub fill: bool,
+    pub color: Option<Color>,
+}
+
+impl Default for Sparkline {
+    fn default() -> Self {
+        Self {
+            style: SparklineStyle::Braille,
+            rows: 1,
+            max_width: None,
+            range: None,
+            fill: false,
+            color: None,
+        }
+    }
+}
+
+impl Sparkline {
+    pub fn new(style: SparklineStyle) -> Self {
+        Self {
+            style,
+            ..Self::default()
+        }
+    }
+
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width.max(1));
+        self
+    }
+
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    pub fn fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    pub fn color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Renderiza la serie; varias filas se separan con `\n` (sin salto final).
+    pub fn render(&self, values: &[f64]) -> String {
+        let mut out = String::new();
+        let _ = self.render_to(&mut out, values);
+        out
+    }
+
+    pub fn render_to<W: Write>(&self, w: &mut W, values: &[f64]) -> fmt::Result {
+        let rows = self.rows.max(1);
+        let per_char = match self.style {
+            SparklineStyle::Braille => 2,
+            SparklineStyle::Blocks => 1,
+        };
+        let samples = match self.max_width {
+            Some(width) => Self::downsample(values, width * per_char),
+            None => values.to_vec(),
+        };
+        if samples.is_empty() {
+            return Ok(());
+        }
+
+        let levels = rows * if per_char == 2 { 4 } else { BLOCKS.len() };
+        let (min, max) = self.range.unwrap_or_else(|| Self::data_range(&samples));
+        let span = max - min;
+        // Nivel de cada muestra en `0..levels`; `None` para huecos.
+        let level = |v: f64| -> Option<usize> {
+            if !v.is_finite() {
+                return None;
+            }
+            let t = if span.abs() < 1e-12 {
+                0.5
+            } else {
+                ((v - min) / span).clamp(0.0, 1.0)
+            };
+            Some(((t * (levels - 1) as f64).round() as usize).min(levels - 1))
+        };
+
+        match self.style {
+            SparklineStyle::Braille => {
+                let width = samples.len().div_ceil(2);
+                let mut canvas = BrailleCanvas::new(width, rows);
+                let mut prev: Option<(isize, isize)> = None;
+                for (x, &v) in samples.iter().enumerate() {
+                    let Some(y) = level(v) else {
+                        prev = None;
+                        continue;
+                    };
+                    let point = (x as isize, y as isize);
+                    if self.fill {
+                        canvas.line(point.0, 0, point.0, point.1, self.color);
+                    }
+                    match prev {
+                        Some((px, py)) => canvas.line(px, py, point.0, point.1, self.color),
+                        None => canvas.set_pixel(x, y, self.color),
+                    }
+                    prev = Some(point);
+                }
+
+                let rendered = canvas.render_with_options(false, None);
+                w.write_str(rendered.trim_end_matches('\n'))
+            }
+            SparklineStyle::Blocks => {
+                if let Some(c) = self.color {
+                    BrailleCanvas::write_ansi_color(w, c)?;
+                }
+                for row in (0..rows).rev() {
+                    for &v in &samples {
+                        // Filas inferiores llenas, la fila del nivel parcial, y vacías encima.
+                        let ch = match level(v) {
+                            None => ' ',
+                            Some(l) => {
+                                let filled = l + 1;
+                                let base = row * BLOCKS.len();
+                                if filled >= base + BLOCKS.len() {
+                                    '█'
+                                } else if filled > base {
+                                    BLOCKS[filled - base - 1]
+                                } else if row == 0 {
+                                    BLOCKS[0]
+                                } else {
+                                    ' '
+                                }
+                            }
+                        };
+                        w.write_char(ch)?;
+                    }
+                    if row > 0 {
+                        w.write_char('\n')?;
+                    }
+                }
+                if self.color.is_some() {
+                    w.write_str("\x1b[0m")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn data_range(values: &[f64]) -> (f64, f64) {
+        values
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            })
+    }
+
+    /// Promedia tramos consecutivos hasta dejar como mucho `target` muestras.
+    fn downsample(values: &[f64], target: usize) -> Vec<f64> {
+        if values.len() <= target {
+            return values.to_vec();
+        }
+        (0..target)
+            .map(|i| {
+                let start = i * values.len() / target;
+                let end = ((i + 1) * values.len() / target).max(start + 1);
+                let finite: Vec<f64> = values[start..end]
+                    .iter()
+                    .copied()
+                    .filter(|v| v.is_finite())
+                    .collect();
+                if finite.is_empty() {
+                    f64::NAN
+                } else {
+                    finite.iter().sum::<f64>() / finite.len() as f64
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sparkline, SparklineStyle};
+
+    #[test]
+    fn blocks_map_min_and_max_to_the_full_height() {
+        let line = Sparkline::new(SparklineStyle::Blocks).render(&[0.0, 1.0, 2.0, 7.0]);
+        assert_eq!(line, "▁▂▃█");
+    }
+
+    #[test]
+    fn two_row_blocks_stack_full_cells_below_partial_ones() {
+        let line = Sparkline::new(SparklineStyle::Blocks)
+            .rows(2)
+            .render(&[0.0, 15.0, 8.0]);
+        assert_eq!(line, " █▁\n▁██");
+    }
+
+    #[test]
+    fn braille_packs_two_samples_per_char_and_honors_max_width() {
+        let line = Sparkline::default().render(&[0.0, 3.0, 3.0, 0.0]);
+        assert_eq!(line.chars().count(), 2);
+        assert_eq!(line, "⡜⢣");
+
+        let long: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let line = Sparkline::default().max_width(10).render(&long);
+        assert_eq!(line.chars().count(), 10);
+    }
+
+    #[test]
+    fn gaps_leave_blank_cells() {
+        let line = Sparkline::new(SparklineStyle::Blocks).render(&[1.0, f64::NAN, 2.0]);
+        assert_eq!(line, "▁ █");
+    }
+}
@@ -0,0 +1,437 @@
+//! Estadística básica compartida por los gráficos de distribución.
+
+use std::f64::consts::PI;
+
+/// Ancho de banda del estimador de densidad por núcleos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bandwidth {
+    /// Regla de Silverman: `0.9 * min(σ, IQR / 1.34) * n^(-1/5)`.
+    Silverman,
+    /// Regla de Scott: `1.06 * σ * n^(-1/5)`.
+    Scott,
+    Fixed(f64),
+}
+
+impl Bandwidth {
+    /// Ancho de banda para `samples` (valores finitos). Con varianza nula se usa
+    /// un ancho pequeño relativo al valor para que la densidad siga siendo visible.
+    pub fn resolve(self, samples: &[f64]) -> f64 {
+        let n = samples.len() as f64;
+        let sigma = std_dev(samples);
+        let h = match self {
+            Self::Fixed(h) => h,
+            Self::Scott => 1.06 * sigma * n.powf(-0.2),
+            Self::Silverman => {
+                let sorted = sorted_finite(samples);
+                let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+                let spread = if iqr > 0.0 {
+                    sigma.min(iqr / 1.34)
+                } else {
+                    sigma
+                };
+                0.9 * spread * n.powf(-0.2)
+            }
+        };
+
+        if h.is_finite() && h > 0.0 {
+            h
+        } else {
+            (mean(samples).abs() * 1e-3).max(1e-3)
+        }
+    }
+}
+
+/// Recta de mínimos cuadrados `y = slope * x + intercept`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coeficiente de determinación sobre los puntos ajustados.
+    pub r_squared: f64,
+}
+
+impl LinearFit {
+    /// Ajusta los puntos finitos; `None` con menos de dos x distintas.
+    pub fn from_points(points: &[(f64, f64)]) -> Option<Self> {
+        let finite: Vec<(f64, f64)> = finite_points(points);
+        let n = finite.len() as f64;
+        let mx = finite.iter().map(|p| p.0).sum::<f64>() / n;
+        let my = finite.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx: f64 = finite.iter().map(|p| (p.0 - mx).powi(2)).sum();
+        let sxy: f64 = finite.iter().map(|p| (p.0 - mx) * (p.1 - my)).sum();
+        if finite.len() < 2 || sxx <= f64::EPSILON * n {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let intercept = my - slope * mx;
+        let fit = Self {
+            slope,
+            intercept,
+            r_squared: 0.0,
+        };
+        Some(Self {
+            r_squared: r_squared(&finite, |x| fit.eval(x)),
+            ..fit
+        })
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+}
+
+/// Polinomio de mínimos cuadrados; `coefficients[k]` multiplica a `x^k`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialFit {
+    pub coefficients: Vec<f64>,
+    pub r_squared: f64,
+}
+
+impl PolynomialFit {
+    /// Ajusta un polinomio de grado `degree` (reducido si no hay suficientes x
+    /// distintas). Se resuelve en `x` centrada y escalada para no perder
+    /// precisión con x grandes y después se expande a potencias de `x`.
+    pub fn from_points(points: &[(f64, f64)], degree: usize) -> Option<Self> {
+        let finite = finite_points(points);
+        let mut distinct: Vec<f64> = finite.iter().map(|p| p.0).collect();
+        distinct.sort_by(f64::total_cmp);
+        distinct.dedup();
+        let degree = degree.min(distinct.len().checked_sub(1)?);
+
+        let xs: Vec<f64> = finite.iter().map(|p| p.0).collect();
+        let center = mean(&xs);
+        let scale = xs
+            .iter()
+            .map(|x| (x - center).abs())
+            .fold(0.0, f64::max)
+            .max(1e-12);
+
+        // Ecuaciones normales (Vandermonde^T * Vandermonde) en t = (x - c) / s.
+        let size = degree + 1;
+        let mut matrix = vec![vec![0.0; size + 1]; size];
+        for &(x, y) in &finite {
+            let t = (x - center) / scale;
+            let powers: Vec<f64> = (0..2 * size).map(|k| t.powi(k as i32)).collect();
+            for (row, cells) in matrix.iter_mut().enumerate() {
+                for (col, cell) in cells.iter_mut().take(size).enumerate() {
+                    *cell += powers[row + col];
+                }
+                cells[size] += powers[row] * y;
+            }
+        }
+        let scaled = solve_linear_system(matrix)?;
+
+        // sum a_k ((x - c) / s)^k  ->  sum b_j x^j
+        let mut coefficients = vec![0.0; size];
+        for (k, a) in scaled.iter().enumerate() {
+            let factor = a / scale.powi(k as i32);
+            let mut binomial = 1.0;
+            for (j, coefficient) in coefficients.iter_mut().enumerate().take(k + 1) {
+                *coefficient += factor * binomial * (-center).powi((k - j) as i32);
+                binomial = binomial * (k - j) as f64 / (j + 1) as f64;
+            }
+        }
+        let mut fit = Self {
+            coefficients,
+            r_squared: 0.0,
+        };
+        fit.r_squared = r_squared(&finite, |x| fit.eval(x));
+        Some(fit)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |acc, c| acc * x + c)
+    }
+}
+
+fn finite_points(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    points
+        .iter()
+        .copied()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect()
+}
+
+fn r_squared(points: &[(f64, f64)], model: impl Fn(f64) -> f64) -> f64 {
+    let ys: Vec<f64> = points.iter().map(|p| p.1).collect();
+    let my = mean(&ys);
+    let ss_tot: f64 = ys.iter().map(|y| (y - my).powi(2)).sum();
+    let ss_res: f64 = points.iter().map(|&(x, y)| (y - model(x)).powi(2)).sum();
+    if ss_tot <= 0.0 {
+        if ss_res <= f64::EPSILON {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        1.0 - ss_res / ss_tot
+    }
+}
+
+/// Eliminación gaussiana con pivoteo parcial sobre la matriz aumentada.
+fn solve_linear_system(mut m: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = m.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        let (upper, lower) = m.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for row in lower.iter_mut() {
+            let f = row[col] / pivot_row[col];
+            for (cell, p) in row.iter_mut().zip(pivot_row).skip(col) {
+                *cell -= f * p;
+            }
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| m[row][k] * x[k]).sum();
+        x[row] = (m[row][n] - tail) / m[row][row];
+    }
+    Some(x)
+}
+
+/// Regresión local lineal (LOESS) en `x` con pesos tricúbicos sobre la
+/// fracción `span` de puntos más cercanos. `points` debe venir ordenado por x.
+pub(crate) fn loess_at(points: &[(f64, f64)], span: f64, x: f64) -> Option<f64> {
+    let n = points.len();
+    let k = ((span.clamp(0.0, 1.0) * n as f64).ceil() as usize).clamp(2.min(n), n);
+    if k == 0 {
+        return None;
+    }
+    let mut distances: Vec<f64> = points.iter().map(|p| (p.0 - x).abs()).collect();
+    distances.sort_by(f64::total_cmp);
+    let radius = distances[k - 1].max(1e-12) * 1.000_001;
+
+    let (mut sw, mut swx, mut swy, mut swxx, mut swxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for &(px, py) in points {
+        let u = (px - x).abs() / radius;
+        if u >= 1.0 {
+            continue;
+        }
+        let w = (1.0 - u.powi(3)).powi(3);
+        sw += w;
+        swx += w * px;
+        swy += w * py;
+        swxx += w * px * px;
+        swxy += w * px * py;
+    }
+    if sw <= 0.0 {
+        return None;
+    }
+    let det = sw * swxx - swx * swx;
+    if det.abs() <= 1e-12 * sw * sw.max(swxx) {
+        // Todos los vecinos en la misma x: media ponderada.
+        return Some(swy / sw);
+    }
+    let slope = (sw * swxy - swx * swy) / det;
+    let intercept = (swy - slope * swx) / sw;
+    Some(intercept + slope * x)
+}
+
+pub(crate) fn sorted_finite(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+pub(crate) fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Desviación típica muestral (n - 1).
+pub(crate) fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(values);
+    let sum_sq: f64 = values.iter().map(|v| (v - m).powi(2)).sum();
+    (sum_sq / (values.len() - 1) as f64).sqrt()
+}
+
+/// Cuantil `p` (0..=1) de una muestra ordenada, interpolando linealmente.
+pub(crate) fn quantile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => f64::NAN,
+        1 => sorted[0],
+        n => {
+            let pos = p.clamp(0.0, 1.0) * (n - 1) as f64;
+            let i = (pos.floor() as usize).min(n - 2);
+            let f = pos - i as f64;
+            sorted[i] + (sorted[i + 1] - sorted[i]) * f
+        }
+    }
+}
+
+/// Cuantil `p` empírico por rango más cercano: el menor valor cuya frecuencia
+/// acumulada alcanza `p`, es decir, el punto donde la ECDF cruza `p`.
+pub(crate) fn nearest_rank(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = (p.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Inversa de la función de distribución normal estándar (aproximación
+/// racional de Acklam, error relativo < 1.2e-9).
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.383_577_518_672_69e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Densidad estimada en `x` con núcleo gaussiano de ancho `h`.
+pub(crate) fn gaussian_kde(samples: &[f64], h: f64, x: f64) -> f64 {
+    if samples.is_empty() || h <= 0.0 {
+        return 0.0;
+    }
+    let norm = 1.0 / (samples.len() as f64 * h * (2.0 * PI).sqrt());
+    samples
+        .iter()
+        .map(|s| {
+            let u = (x - s) / h;
+            (-0.5 * u * u).exp()
+        })
+        .sum::<f64>()
+        * norm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        gaussian_kde, loess_at, nearest_rank, normal_quantile, quantile, Bandwidth, LinearFit,
+        PolynomialFit,
+    };
+
+    #[test]
+    fn linear_fit_recovers_an_exact_line() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)];
+        let fit = LinearFit::from_points(&points).unwrap();
+        assert!((fit.slope - 2.0).abs() < 1e-12);
+        assert!((fit.intercept - 1.0).abs() < 1e-12);
+        assert!((fit.r_squared - 1.0).abs() < 1e-12);
+        assert!(LinearFit::from_points(&[(1.0, 1.0), (1.0, 2.0)]).is_none());
+    }
+
+    #[test]
+    fn polynomial_fit_recovers_a_parabola_far_from_the_origin() {
+        let points: Vec<(f64, f64)> = (0..20)
+            .map(|i| {
+                let x = 1000.0 + i as f64;
+                (x, 0.5 * x * x - 3.0 * x + 2.0)
+            })
+            .collect();
+        let fit = PolynomialFit::from_points(&points, 2).unwrap();
+        assert_eq!(fit.degree(), 2);
+        for &(x, y) in &points {
+            assert!((fit.eval(x) - y).abs() / y < 1e-9);
+        }
+    }
+
+    #[test]
+    fn loess_follows_a_straight_line_exactly() {
+        let points: Vec<(f64, f64)> = (0..10).map(|i| (i as f64, 3.0 * i as f64)).collect();
+        assert!((loess_at(&points, 0.5, 4.5).unwrap() - 13.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quantile_interpolates_between_samples() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(quantile(&sorted, 0.0), 1.0);
+        assert_eq!(quantile(&sorted, 0.5), 2.5);
+        assert_eq!(quantile(&sorted, 1.0), 4.0);
+    }
+
+    #[test]
+    fn nearest_rank_matches_the_ecdf_steps() {
+        let sorted = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(nearest_rank(&sorted, 0.5), 20.0);
+        assert_eq!(nearest_rank(&sorted, 0.51), 30.0);
+        assert_eq!(nearest_rank(&sorted, 0.99), 40.0);
+    }
+
+    #[test]
+    fn normal_quantile_hits_known_values() {
+        assert!(normal_quantile(0.5).abs() < 1e-9);
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-6);
+        assert!((normal_quantile(0.001) + 3.090232).abs() < 1e-6);
+    }
+
+    #[test]
+    fn kde_integrates_to_one() {
+        let samples = [0.0, 0.5, 1.0, 4.0];
+        let h = Bandwidth::Silverman.resolve(&samples);
+        let step = 0.01;
+        let area: f64 = (-2000..2000)
+            .map(|i| gaussian_kde(&samples, h, i as f64 * step) * step)
+            .sum();
+        assert!((area - 1.0).abs() < 1e-3, "area = {area}");
+    }
+
+    #[test]
+    fn constant_samples_get_a_positive_bandwidth() {
+        assert!(Bandwidth::Scott.resolve(&[3.0, 3.0, 3.0]) > 0.0);
+    }
+}
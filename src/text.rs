@@ -0,0 +1,267 @@
+//! Ancho de texto en celdas de terminal.
+//!
+//! La capa de texto del lienzo trabaja por grafemas, no por `char`: los glifos
+//! anchos (CJK, emoji) ocupan dos celdas y las marcas combinantes se quedan en
+//! la celda de su carácter base. Las tablas cubren los rangos habituales de
+//! `EastAsianWidth` y `Extend`, sin pretender ser una implementación completa
+//! de UAX #11 / UAX #29.
+
+/// Rangos de ancho cero: marcas combinantes, selectores de variante,
+/// modificadores de tono de piel y caracteres de formato.
+#[rustfmt::skip]
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F), (0x0483, 0x0489), (0x0591, 0x05BD), (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2), (0x05C4, 0x05C5), (0x05C7, 0x05C7), (0x0610, 0x061A),
+    (0x064B, 0x065F), (0x0670, 0x0670), (0x06D6, 0x06DC), (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8), (0x06EA, 0x06ED), (0x0900, 0x0902), (0x093A, 0x093A),
+    (0x093C, 0x093C), (0x0941, 0x0948), (0x094D, 0x094D), (0x0951, 0x0957),
+    (0x0E31, 0x0E31), (0x0E34, 0x0E3A), (0x0E47, 0x0E4E), (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF), (0x200B, 0x200F), (0x202A, 0x202E), (0x2060, 0x2064),
+    (0x20D0, 0x20FF), (0x302A, 0x302F), (0x3099, 0x309A), (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F), (0xFEFF, 0xFEFF), (0x1F3FB, 0x1F3FF), (0xE0020, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Rangos de ancho doble (`EastAsianWidth` W/F y emoji de presentación gráfica).
+#[rustfmt::skip]
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F), (0x231A, 0x231B), (0x2329, 0x232A), (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0), (0x23F3, 0x23F3), (0x25FD, 0x25FE), (0x2614, 0x2615),
+    (0x2648, 0x2653), (0x267F, 0x267F), (0x2693, 0x2693), (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB), (0x26BD, 0x26BE), (0x26C4, 0x26C5), (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4), (0x26EA, 0x26EA), (0x26F2, 0x26F3), (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA), (0x26FD, 0x26FD), (0x2705, 0x2705), (0x270A, 0x270B),
+    (0x2728, 0x2728), (0x274C, 0x274C), (0x274E, 0x274E), (0x2753, 0x2755),
+    (0x2757, 0x2757), (0x2795, 0x2797), (0x27B0, 0x27B0), (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C), (0x2B50, 0x2B50), (0x2B55, 0x2B55), (0x2E80, 0x303E),
+    (0x3041, 0x33FF), (0x3400, 0x4DBF), (0x4E00, 0x9FFF), (0xA000, 0xA4CF),
+    (0xA960, 0xA97F), (0xAC00, 0xD7A3), (0xF900, 0xFAFF), (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F), (0xFF00, 0xFF60), (0xFFE0, 0xFFE6), (0x16FE0, 0x16FE4),
+    (0x17000, 0x18AFF), (0x1B000, 0x1B2FF), (0x1F004, 0x1F004), (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F1E6, 0x1F1FF), (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F), (0x1F680, 0x1F6FF), (0x1F7E0, 0x1F7EB), (0x1F900, 0x1F9FF),
+    (0x1FA70, 0x1FAFF), (0x20000, 0x3FFFD),
+];
+
+/// Alineación horizontal de un texto respecto a su ancla o a su caja.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HAlign {
+    #[default]
+    Left,
+    Center,
+    /// El último glifo termina en la columna del ancla.
+    Right,
+}
+
+/// Alineación vertical de un bloque de texto respecto a su ancla o a su caja.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VAlign {
+    /// La primera línea va en la fila del ancla y el resto debajo.
+    Top,
+    Middle,
+    /// La última línea va en la fila del ancla y el resto encima.
+    #[default]
+    Bottom,
+}
+
+const ZWJ: char = '\u{200D}';
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+fn in_table(table: &[(u32, u32)], c: char) -> bool {
+    let c = c as u32;
+    table
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                std::cmp::Ordering::Less
+            } else if lo > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Celdas que ocupa `c` por sí solo: 0, 1 o 2. Los caracteres de control
+/// cuentan como 0 porque el lienzo nunca los escribe.
+pub fn char_width(c: char) -> usize {
+    if c.is_control() || c == ZWJ || in_table(ZERO_WIDTH, c) {
+        0
+    } else if in_table(WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Celdas que ocupa un grafema: las de su carácter base, o 2 si pide
+/// presentación emoji (`U+FE0F`).
+pub fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    let Some(base) = chars.next() else {
+        return 0;
+    };
+    match char_width(base) {
+        1 if chars.any(|c| c == EMOJI_PRESENTATION) => 2,
+        w => w,
+    }
+}
+
+/// Ancho de `text` en celdas de terminal.
+pub fn display_width(text: &str) -> usize {
+    graphemes(text).map(grapheme_width).sum()
+}
+
+/// Divide `text` en grafemas: un carácter base con sus marcas de ancho cero,
+/// secuencias unidas por ZWJ y pares de indicadores regionales (banderas).
+pub fn graphemes(text: &str) -> impl Iterator<Item = &str> + '_ {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let mut iter = rest.char_indices();
+        let (_, first) = iter.next()?;
+        let mut end = first.len_utf8();
+        let mut joined = false;
+        let mut pending_flag = is_regional_indicator(first);
+        for (i, c) in iter {
+            let extends = if joined {
+                true
+            } else if pending_flag && is_regional_indicator(c) {
+                pending_flag = false;
+                true
+            } else {
+                c == ZWJ || char_width(c) == 0 && !c.is_control()
+            };
+            if !extends {
+                break;
+            }
+            joined = c == ZWJ;
+            end = i + c.len_utf8();
+        }
+        let (grapheme, tail) = rest.split_at(end);
+        rest = tail;
+        Some(grapheme)
+    })
+}
+
+/// Recorta `text` a `max` celdas, terminando en `…` si no cabe. Nunca parte un
+/// grafema ni deja medio glifo ancho.
+pub fn truncate(text: &str, max: usize) -> String {
+    if display_width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for g in graphemes(text) {
+        let w = grapheme_width(g);
+        if used + w > max - 1 {
+            break;
+        }
+        out.push_str(g);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+/// Reparte `text` en líneas de como mucho `width` celdas, cortando por espacios.
+/// Respeta los `\n` y parte por grafemas las palabras que no caben solas; un
+/// grafema más ancho que `width` (un glifo ancho con `width == 1`) se sustituye
+/// por `…`, como en `truncate`.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut used = 0;
+        for word in paragraph.split_whitespace() {
+            let w = display_width(word);
+            if used > 0 && used + 1 + w <= width {
+                line.push(' ');
+                line.push_str(word);
+                used += 1 + w;
+                continue;
+            }
+            if used > 0 {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            if w <= width {
+                line.push_str(word);
+                used = w;
+                continue;
+            }
+            for g in graphemes(word) {
+                let (g, gw) = match grapheme_width(g) {
+                    gw if gw > width => ("…", 1),
+                    gw => (g, gw),
+                };
+                if used > 0 && used + gw > width {
+                    lines.push(std::mem::take(&mut line));
+                    used = 0;
+                }
+                line.push_str(g);
+                used += gw;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{display_width, graphemes, truncate, wrap};
+
+    #[test]
+    fn wide_glyphs_count_double_and_marks_count_zero() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("🚀 ok"), 5);
+        assert_eq!(display_width("❤\u{FE0F}"), 2);
+    }
+
+    #[test]
+    fn graphemes_keep_marks_joiners_and_flags_together() {
+        let parts: Vec<&str> = graphemes("ae\u{301}👩\u{200D}💻🇪🇸x").collect();
+        assert_eq!(parts, ["a", "e\u{301}", "👩\u{200D}💻", "🇪🇸", "x"]);
+    }
+
+    #[test]
+    fn truncation_never_splits_a_wide_glyph() {
+        assert_eq!(truncate("日本語", 6), "日本語");
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(truncate("cafe\u{301}s", 5), "cafe\u{301}s");
+        assert_eq!(truncate("cafe\u{301}s", 4), "caf…");
+    }
+
+    #[test]
+    fn wrap_breaks_on_spaces_and_splits_long_words() {
+        assert_eq!(
+            wrap("uso de cpu alto\nred", 7),
+            ["uso de", "cpu", "alto", "red"]
+        );
+        assert_eq!(
+            wrap("supercalifragilistico", 8),
+            ["supercal", "ifragili", "stico"]
+        );
+        assert_eq!(wrap("日本語テ", 5), ["日本", "語テ"]);
+    }
+
+    #[test]
+    fn wrap_never_exceeds_a_width_narrower_than_a_wide_glyph() {
+        assert_eq!(wrap("日本", 1), ["…", "…"]);
+        assert_eq!(wrap("a日b", 1), ["a", "…", "b"]);
+        assert!(wrap("日本 語", 1).iter().all(|l| display_width(l) <= 1));
+    }
+}
@@ -0,0 +1,379 @@
+//! Widgets compactos de cuadro de mando: indicador semicircular, barra de
+//! progreso y bullet chart. Como `Sparkline`, se configuran con métodos
+//! encadenables y se renderizan a texto sin bordes.
+
+use crate::canvas::BrailleCanvas;
+use crate::scale::AxisScale;
+use crate::text;
+use colored::Color;
+use std::f64::consts::PI;
+use std::fmt::{self, Write};
+
+/// Color de la zona que contiene `value`; cada zona es `(límite_superior, color)`
+/// y empieza donde acaba la anterior.
+fn zone_color(zones: &[(f64, Option<Color>)], value: f64) -> (Option<usize>, Option<Color>) {
+    zones
+        .iter()
+        .position(|(upper, _)| value <= *upper)
+        .map_or((None, None), |i| (Some(i), zones[i].1))
+}
+
+fn normalized(value: f64, min: f64, max: f64) -> f64 {
+    let span = max - min;
+    if span.abs() < 1e-12 || !value.is_finite() {
+        return 0.0;
+    }
+    ((value - min) / span).clamp(0.0, 1.0)
+}
+
+fn write_canvas<W: Write>(w: &mut W, canvas: &BrailleCanvas) -> fmt::Result {
+    let rendered = canvas.render_with_options(false, None);
+    w.write_str(rendered.trim_end_matches('\n'))
+}
+
+/// Indicador semicircular con aguja: el mínimo queda a la izquierda y el
+/// máximo a la derecha, con el arco coloreado por zonas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gauge {
+    pub min: f64,
+    pub max: f64,
+    /// Zonas `(límite_superior, color)` en orden creciente.
+    pub zones: Vec<(f64, Option<Color>)>,
+    pub width: usize,
+    pub rows: usize,
+    pub needle_color: Option<Color>,
+    /// Escribe el valor centrado en una fila bajo el arco.
+    pub show_value: bool,
+}
+
+impl Default for Gauge {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 100.0,
+            zones: Vec::new(),
+            width: 16,
+            rows: 5,
+            needle_color: None,
+            show_value: true,
+        }
+    }
+}
+
+impl Gauge {
+    pub fn new(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            ..Self::default()
+        }
+    }
+
+    pub fn zone(mut self, upper: f64, color: Option<Color>) -> Self {
+        self.zones.push((upper, color));
+        self
+    }
+
+    pub fn size(mut self, width: usize, rows: usize) -> Self {
+        self.width = width.max(2);
+        self.rows = rows.max(1);
+        self
+    }
+
+    pub fn needle_color(mut self, color: Option<Color>) -> Self {
+        self.needle_color = color;
+        self
+    }
+
+    pub fn show_value(mut self, show: bool) -> Self {
+        self.show_value = show;
+        self
+    }
+
+    pub fn render(&self, value: f64) -> String {
+        let mut out = String::new();
+        let _ = self.render_to(&mut out, value);
+        out
+    }
+
+    pub fn render_to<W: Write>(&self, w: &mut W, value: f64) -> fmt::Result {
+        // Los campos son públicos: se aplican aquí los mismos mínimos que `size`.
+        let (width, rows) = (self.width.max(2), self.rows.max(1));
+        let text_rows = usize::from(self.show_value && rows > 1);
+        let mut canvas = BrailleCanvas::new(width, rows);
+
+        let cx = (canvas.pixel_width() - 1) as f64 / 2.0;
+        let cy = (text_rows * 4) as f64;
+        let radius = cx.min(((rows - text_rows) * 4 - 1) as f64);
+
+        // Arco de dos píxeles de grosor, coloreado según la zona del valor.
+        let steps = (PI * radius * 2.0).ceil().max(8.0) as usize;
+        for i in 0..=steps {
+            let t = i as f64 / steps as f64;
+            let (_, color) = zone_color(&self.zones, self.min + t * (self.max - self.min));
+            let angle = PI * (1.0 - t);
+            for r in [radius, radius - 1.0] {
+                let x = (cx + r * angle.cos()).round();
+                let y = (cy + r * angle.sin()).round();
+                if x >= 0.0 && y >= 0.0 {
+                    canvas.set_pixel(x as usize, y as usize, color);
+                }
+            }
+        }
+
+        let angle = PI * (1.0 - normalized(value, self.min, self.max));
+        let length = (radius - 3.0).max(1.0);
+        let (x0, y0) = (cx.round() as isize, cy as isize);
+        let x1 = (cx + length * angle.cos()).round() as isize;
+        let y1 = (cy + length * angle.sin()).round() as isize;
+        canvas.line(x0, y0, x1, y1, self.needle_color);
+        canvas.circle_filled(x0, y0, 1, self.needle_color);
+
+        if text_rows > 0 {
+            let text = AxisScale::format_compact(value);
+            let start = (width / 2).saturating_sub(text::display_width(&text) / 2);
+            canvas.set_str(start, 0, &text, self.needle_color);
+        }
+        write_canvas(w, &canvas)
+    }
+}
+
+/// Barra de progreso horizontal con 8 pasos por carácter: cada celda Braille se
+/// llena columna a columna y de abajo arriba.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressBar {
+    pub width: usize,
+    pub color: Option<Color>,
+    /// Marca la parte pendiente con una línea fina (`⣀`).
+    pub track: bool,
+    /// Añade el porcentaje tras la barra.
+    pub show_percent: bool,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            width: 20,
+            color: None,
+            track: false,
+            show_percent: false,
+        }
+    }
+}
+
+impl ProgressBar {
+    pub fn new(width: usize) -> Self {
+        Self {
+            width: width.max(1),
+            ..Self::default()
+        }
+    }
+
+    pub fn color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn track(mut self, track: bool) -> Self {
+        self.track = track;
+        self
+    }
+
+    pub fn show_percent(mut self, show: bool) -> Self {
+        self.show_percent = show;
+        self
+    }
+
+    /// `fraction` en 0..=1 (se recorta fuera de ese rango).
+    pub fn render(&self, fraction: f64) -> String {
+        let mut out = String::new();
+        let _ = self.render_to(&mut out, fraction);
+        out
+    }
+
+    pub fn render_to<W: Write>(&self, w: &mut W, fraction: f64) -> fmt::Result {
+        let fraction = normalized(fraction, 0.0, 1.0);
+        let mut canvas = BrailleCanvas::new(self.width, 1);
+        let dots = (fraction * (self.width * 8) as f64).round() as usize;
+
+        for level in 0..dots {
+            let cell = level / 8;
+            let within = level % 8;
+            canvas.set_pixel(cell * 2 + within / 4, within % 4, self.color);
+        }
+        if self.track {
+            for cell in dots.div_ceil(8)..self.width {
+                canvas.fill_span(cell as isize * 2, cell as isize * 2 + 1, 0, None, true);
+            }
+        }
+
+        write_canvas(w, &canvas)?;
+        if self.show_percent {
+            write!(w, " {:>3.0}%", fraction * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bullet chart: barra del valor sobre bandas cualitativas (texturas de
+/// distinta densidad además del color) y una marca vertical de objetivo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulletChart {
+    pub min: f64,
+    pub max: f64,
+    /// Bandas `(límite_superior, color)` en orden creciente.
+    pub bands: Vec<(f64, Option<Color>)>,
+    pub width: usize,
+    pub rows: usize,
+    pub bar_color: Option<Color>,
+    pub target_color: Option<Color>,
+}
+
+impl Default for BulletChart {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 100.0,
+            bands: Vec::new(),
+            width: 20,
+            rows: 1,
+            bar_color: None,
+            target_color: None,
+        }
+    }
+}
+
+impl BulletChart {
+    pub fn new(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            ..Self::default()
+        }
+    }
+
+    pub fn band(mut self, upper: f64, color: Option<Color>) -> Self {
+        self.bands.push((upper, color));
+        self
+    }
+
+    pub fn size(mut self, width: usize, rows: usize) -> Self {
+        self.width = width.max(1);
+        self.rows = rows.max(1);
+        self
+    }
+
+    pub fn bar_color(mut self, color: Option<Color>) -> Self {
+        self.bar_color = color;
+        self
+    }
+
+    pub fn target_color(mut self, color: Option<Color>) -> Self {
+        self.target_color = color;
+        self
+    }
+
+    pub fn render(&self, value: f64, target: f64) -> String {
+        let mut out = String::new();
+        let _ = self.render_to(&mut out, value, target);
+        out
+    }
+
+    pub fn render_to<W: Write>(&self, w: &mut W, value: f64, target: f64) -> fmt::Result {
+        // Los campos son públicos: se aplican aquí los mismos mínimos que `size`.
+        let mut canvas = BrailleCanvas::new(self.width.max(1), self.rows.max(1));
+        let width_px = canvas.pixel_width();
+        let height_px = canvas.pixel_height();
+        let last_px = (width_px - 1) as f64;
+        let to_px = |v: f64| (normalized(v, self.min, self.max) * last_px).round() as isize;
+
+        // La barra ocupa el tercio central; las bandas, el resto de la altura.
+        let bar_low = height_px / 3;
+        let bar_high = height_px - height_px / 3 - 1;
+
+        for x in 0..width_px {
+            let v = self.min + (self.max - self.min) * x as f64 / last_px.max(1.0);
+            let (Some(band), color) = zone_color(&self.bands, v) else {
+                continue;
+            };
+            for y in (0..height_px).filter(|y| *y < bar_low || *y > bar_high) {
+                // Ninguna textura es sólida para que la barra siga destacando.
+                if (x + y) % (band + 2) == 0 {
+                    canvas.set_pixel(x, y, color);
+                }
+            }
+        }
+
+        if value.is_finite() && value > self.min {
+            canvas.rect_filled(
+                0,
+                (height_px - 1 - bar_high) as isize,
+                to_px(value) as usize + 1,
+                bar_high - bar_low + 1,
+                self.bar_color,
+            );
+        }
+        if target.is_finite() {
+            let x = to_px(target);
+            canvas.line(x, 0, x, height_px as isize - 1, self.target_color);
+        }
+        write_canvas(w, &canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BulletChart, Gauge, ProgressBar};
+
+    #[test]
+    fn progress_fills_eight_steps_per_cell() {
+        assert_eq!(ProgressBar::new(4).render(0.5), "⣿⣿⠀⠀");
+        assert_eq!(ProgressBar::new(2).render(10.0 / 16.0), "⣿⡄");
+        assert_eq!(ProgressBar::new(3).track(true).render(0.0), "⣀⣀⣀");
+        assert_eq!(
+            ProgressBar::new(2).show_percent(true).render(1.0),
+            "⣿⣿ 100%"
+        );
+    }
+
+    #[test]
+    fn bullet_draws_the_bar_and_the_target() {
+        let bullet = BulletChart::new(0.0, 10.0).size(6, 1);
+        assert_eq!(bullet.render(5.0, 8.0), "⠶⠶⠶⠆⢸⠀");
+    }
+
+    #[test]
+    fn gauge_needle_swings_from_left_to_right() {
+        let gauge = Gauge::new(0.0, 10.0).size(10, 4).show_value(false);
+        let lit_left = |s: &str| {
+            let row: Vec<char> = s.lines().last().unwrap().chars().collect();
+            let left = row[1..4].iter().filter(|c| **c != '⠀').count();
+            let right = row[6..9].iter().filter(|c| **c != '⠀').count();
+            (left, right)
+        };
+        let (left, right) = lit_left(&gauge.render(0.0));
+        assert!(left > right);
+        let (left, right) = lit_left(&gauge.render(10.0));
+        assert!(right > left);
+
+        let with_value = Gauge::new(0.0, 10.0).size(10, 4).render(7.5);
+        assert!(with_value.lines().last().unwrap().contains("7.5"));
+    }
+
+    #[test]
+    fn zero_sized_widgets_render_at_their_minimum_size() {
+        let gauge = Gauge {
+            width: 0,
+            rows: 0,
+            ..Gauge::default()
+        };
+        assert_eq!(gauge.render(50.0).chars().count(), 2);
+
+        let bullet = BulletChart {
+            width: 0,
+            rows: 0,
+            ..BulletChart::default()
+        };
+        assert_eq!(bullet.render(50.0, 80.0).chars().count(), 1);
+    }
+}